@@ -0,0 +1,293 @@
+//! Content-addressed storage for file bodies carried by [`EnvironmentSnapshot`]s.
+//!
+//! `Transition::before`/`after` each hold a full [`EnvironmentSnapshot`], and
+//! most files don't change between one turn's `after` and the next turn's
+//! `before` — so serializing every snapshot's file bodies inline duplicates
+//! whole file contents across a conversation's history. `BlobStore` factors
+//! bodies out by content hash so `Conversation::save`/`load` write each
+//! distinct body exactly once, no matter how many snapshots reference it.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use super::{EnvironmentSnapshot, Transition};
+
+/// Hash -> file body, deduplicated by content.
+pub type BlobStore = HashMap<String, String>;
+
+/// Directory name, under the workspace, holding one file per distinct blob
+/// hash. Mirrors [`super::recorder::TransitionRecorder`]'s own
+/// `.claude-sdk/transitions` convention.
+const CAS_DIR: &str = ".claude-sdk/blobs";
+
+/// On-disk content-addressed store for file bodies, shared by every
+/// [`super::Conversation`] saved against one workspace — two conversations
+/// touching the same files write that body to disk exactly once between
+/// them, instead of once per saved conversation file.
+///
+/// Complements [`extract_blobs`]/[`hydrate`], which dedupe bodies within a
+/// single conversation's own saved JSON: `CasStore` additionally persists
+/// those bodies to `{workspace}/.claude-sdk/blobs/{hash}` so they survive
+/// independently of any one conversation file and can be reused or garbage
+/// collected across all of them.
+pub struct CasStore {
+    dir: PathBuf,
+}
+
+impl CasStore {
+    pub fn new(workspace: &Path) -> Self {
+        Self { dir: workspace.join(CAS_DIR) }
+    }
+
+    /// Write every blob in `blobs` not already present on disk.
+    pub fn write_all(&self, blobs: &BlobStore) -> io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        for (hash, body) in blobs {
+            let path = self.dir.join(hash);
+            if !path.exists() {
+                fs::write(path, body)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Read a single blob back by hash.
+    pub fn read(&self, hash: &str) -> io::Result<String> {
+        fs::read_to_string(self.dir.join(hash))
+    }
+
+    /// Refill a single transition's stripped `FileSnapshot::body` fields by
+    /// reading each referenced hash back from disk. The read-side
+    /// counterpart to [`extract_blobs`] for callers — like
+    /// [`super::recorder::TransitionRecorder`] — that persist straight to
+    /// the CAS instead of keeping an in-memory [`BlobStore`] around. A
+    /// missing blob is left as `None` rather than erroring, since a
+    /// transition whose file never had a body (e.g. elided by
+    /// [`super::observer::SnapshotConfig`]) looks the same on disk as one
+    /// whose blob genuinely isn't present.
+    pub fn hydrate_transition(&self, transition: &mut Transition) {
+        for snapshot in [&mut transition.before, &mut transition.after] {
+            for file in snapshot.files.values_mut() {
+                if file.body.is_none() {
+                    if let Ok(body) = self.read(&file.hash) {
+                        file.body = Some(body);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Delete every blob on disk whose hash isn't in `referenced`, returning
+    /// how many were removed. Callers should compute `referenced` from every
+    /// conversation that might draw on this workspace's CAS, not just one,
+    /// since a blob unique to a different conversation would otherwise be
+    /// deleted out from under it.
+    pub fn gc(&self, referenced: &HashSet<String>) -> io::Result<usize> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(0),
+            Err(e) => return Err(e),
+        };
+
+        let mut removed = 0;
+        for entry in entries {
+            let entry = entry?;
+            let hash = entry.file_name().to_string_lossy().into_owned();
+            if !referenced.contains(&hash) {
+                fs::remove_file(entry.path())?;
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+}
+
+/// Strip inline bodies out of `transitions`, returning a copy with every
+/// `FileSnapshot::body` cleared (recoverable from `hash`) plus the
+/// deduplicated blob store needed to rehydrate them with [`hydrate`].
+pub fn extract_blobs(transitions: &[Transition]) -> (Vec<Transition>, BlobStore) {
+    let mut blobs = BlobStore::new();
+    let mut stripped = transitions.to_vec();
+
+    for transition in &mut stripped {
+        for snapshot in [&mut transition.before, &mut transition.after] {
+            for file in snapshot.files.values_mut() {
+                if let Some(body) = file.body.take() {
+                    blobs.entry(file.hash.clone()).or_insert(body);
+                }
+            }
+        }
+    }
+
+    (stripped, blobs)
+}
+
+/// Inverse of [`extract_blobs`]: refill every `FileSnapshot::body` from
+/// `blobs` by hash, so callers see fully-hydrated snapshots regardless of
+/// how they were stored on disk.
+pub fn hydrate(transitions: &mut [Transition], blobs: &BlobStore) {
+    for transition in transitions {
+        for snapshot in [&mut transition.before, &mut transition.after] {
+            hydrate_snapshot(snapshot, blobs);
+        }
+    }
+}
+
+fn hydrate_snapshot(snapshot: &mut EnvironmentSnapshot, blobs: &BlobStore) {
+    for file in snapshot.files.values_mut() {
+        if file.body.is_none() {
+            if let Some(body) = blobs.get(&file.hash) {
+                file.body = Some(body.clone());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execution::{ClaudeExecution, ClaudePrompt, FileSnapshot};
+    use chrono::Utc;
+    use std::path::PathBuf;
+    use uuid::Uuid;
+
+    fn file(hash: &str, body: &str) -> FileSnapshot {
+        FileSnapshot {
+            hash: hash.to_string(),
+            size: body.len() as u64,
+            body: Some(body.to_string()),
+        }
+    }
+
+    fn snapshot(files: &[(&str, FileSnapshot)]) -> EnvironmentSnapshot {
+        EnvironmentSnapshot {
+            files: files
+                .iter()
+                .map(|(path, f)| (PathBuf::from(path), f.clone()))
+                .collect(),
+            session_file: PathBuf::from("session.jsonl"),
+            session_id: None,
+            timestamp: Utc::now(),
+            session: None,
+        }
+    }
+
+    fn transition(before: EnvironmentSnapshot, after: EnvironmentSnapshot) -> Transition {
+        Transition {
+            id: Uuid::new_v4(),
+            before,
+            prompt: ClaudePrompt::default(),
+            execution: ClaudeExecution {
+                prompt: ClaudePrompt::default(),
+                response: String::new(),
+                session_id: "session-1".to_string(),
+                cost: 0.0,
+                duration_ms: 0,
+                model: "claude".to_string(),
+                timestamp: Utc::now(),
+            },
+            after,
+            recorded_at: Utc::now(),
+            metadata: Default::default(),
+        }
+    }
+
+    #[test]
+    fn extract_blobs_strips_bodies_and_dedupes_by_hash_across_snapshots() {
+        // "a.txt" is unchanged between before and after, so its body should
+        // only be stored once under its shared hash, not once per snapshot.
+        let shared = file("hash-a", "unchanged");
+        let before = snapshot(&[("a.txt", shared.clone()), ("b.txt", file("hash-b", "old b"))]);
+        let after = snapshot(&[("a.txt", shared), ("b.txt", file("hash-b2", "new b"))]);
+        let transitions = vec![transition(before, after)];
+
+        let (stripped, blobs) = extract_blobs(&transitions);
+
+        assert_eq!(blobs.len(), 3);
+        assert_eq!(blobs.get("hash-a"), Some(&"unchanged".to_string()));
+        assert_eq!(blobs.get("hash-b"), Some(&"old b".to_string()));
+        assert_eq!(blobs.get("hash-b2"), Some(&"new b".to_string()));
+
+        for snap in [&stripped[0].before, &stripped[0].after] {
+            for f in snap.files.values() {
+                assert!(f.body.is_none());
+            }
+        }
+    }
+
+    #[test]
+    fn hydrate_is_the_inverse_of_extract_blobs() {
+        let before = snapshot(&[("a.txt", file("hash-a", "hello"))]);
+        let after = snapshot(&[("a.txt", file("hash-a", "hello")), ("b.txt", file("hash-b", "world"))]);
+        let original = vec![transition(before, after)];
+
+        let (mut stripped, blobs) = extract_blobs(&original);
+        hydrate(&mut stripped, &blobs);
+
+        assert_eq!(
+            stripped[0].before.files.get(&PathBuf::from("a.txt")).unwrap().body,
+            Some("hello".to_string())
+        );
+        assert_eq!(
+            stripped[0].after.files.get(&PathBuf::from("b.txt")).unwrap().body,
+            Some("world".to_string())
+        );
+    }
+
+    #[test]
+    fn hydrate_leaves_unknown_hashes_unrecovered() {
+        let mut snap = snapshot(&[("a.txt", FileSnapshot {
+            hash: "missing-hash".to_string(),
+            size: 0,
+            body: None,
+        })]);
+        let blobs = BlobStore::new();
+        hydrate_snapshot(&mut snap, &blobs);
+
+        assert!(snap.files.get(&PathBuf::from("a.txt")).unwrap().body.is_none());
+    }
+
+    #[test]
+    fn cas_store_write_all_then_read_round_trips_and_skips_existing_blobs() {
+        let tmp = std::env::temp_dir().join(format!("claude_sdk_cas_test_{}", Uuid::new_v4()));
+        let workspace = tmp.as_path();
+        let cas = CasStore::new(workspace);
+
+        let mut blobs = BlobStore::new();
+        blobs.insert("hash-a".to_string(), "hello".to_string());
+        cas.write_all(&blobs).unwrap();
+        assert_eq!(cas.read("hash-a").unwrap(), "hello");
+
+        // Writing again with a different body for the same hash must not
+        // overwrite the blob already on disk under that hash.
+        let mut blobs2 = BlobStore::new();
+        blobs2.insert("hash-a".to_string(), "different".to_string());
+        cas.write_all(&blobs2).unwrap();
+        assert_eq!(cas.read("hash-a").unwrap(), "hello");
+
+        fs::remove_dir_all(&tmp).ok();
+    }
+
+    #[test]
+    fn cas_store_gc_removes_only_unreferenced_blobs() {
+        let tmp = std::env::temp_dir().join(format!("claude_sdk_cas_gc_test_{}", Uuid::new_v4()));
+        let workspace = tmp.as_path();
+        let cas = CasStore::new(workspace);
+
+        let mut blobs = BlobStore::new();
+        blobs.insert("keep".to_string(), "keep me".to_string());
+        blobs.insert("drop".to_string(), "drop me".to_string());
+        cas.write_all(&blobs).unwrap();
+
+        let referenced: HashSet<String> = ["keep".to_string()].into_iter().collect();
+        let removed = cas.gc(&referenced).unwrap();
+
+        assert_eq!(removed, 1);
+        assert!(cas.read("keep").is_ok());
+        assert!(cas.read("drop").is_err());
+
+        fs::remove_dir_all(&tmp).ok();
+    }
+}
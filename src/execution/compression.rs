@@ -0,0 +1,126 @@
+//! Token estimation for context-window-aware conversation compression.
+//!
+//! `Conversation` uses this to decide when accumulated history is getting
+//! close to the model's context window and should be summarized down before
+//! the next `send`. The default estimator is a cheap chars/4 heuristic;
+//! callers that know their model's real tokenizer can plug in a more
+//! accurate one via `Conversation::set_token_estimator`.
+
+use chrono::Utc;
+use uuid::Uuid;
+
+use super::{ClaudeExecution, ClaudePrompt, Transition, TransitionMetadata};
+
+/// Estimates how many tokens a piece of text will cost.
+pub trait TokenEstimator: Send + Sync {
+    fn estimate(&self, text: &str) -> usize;
+}
+
+/// Default estimator: roughly 4 characters per token, the same rule of thumb
+/// most tokenizer-free heuristics use.
+pub struct CharsPerTokenEstimator {
+    pub chars_per_token: usize,
+}
+
+impl Default for CharsPerTokenEstimator {
+    fn default() -> Self {
+        Self { chars_per_token: 4 }
+    }
+}
+
+impl TokenEstimator for CharsPerTokenEstimator {
+    fn estimate(&self, text: &str) -> usize {
+        text.chars().count() / self.chars_per_token.max(1)
+    }
+}
+
+/// Outcome of a single auto-compression pass.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompressionReport {
+    /// How many of the oldest transitions were folded into the summary.
+    pub transitions_compacted: usize,
+    /// Estimated token count of the history before compaction.
+    pub tokens_before: usize,
+    /// Estimated token count of the history after compaction.
+    pub tokens_after: usize,
+}
+
+/// Sum of `estimator`'s token estimate over every prompt/response pair in
+/// `transitions`.
+pub fn estimate_tokens(transitions: &[Transition], estimator: &dyn TokenEstimator) -> usize {
+    transitions
+        .iter()
+        .map(|t| estimator.estimate(&t.prompt.text) + estimator.estimate(&t.execution.response))
+        .sum()
+}
+
+/// Collapse all but the most recent `keep_recent` transitions into a single
+/// synthetic summary transition.
+///
+/// The synthetic transition's `before`/`after` snapshots are taken from the
+/// first and last compacted transitions, so the environment snapshot chain
+/// still lines up with whatever remains. Returns `None` if there aren't more
+/// than `keep_recent` transitions to compact.
+pub fn compact_transitions(
+    transitions: &[Transition],
+    keep_recent: usize,
+    estimator: &dyn TokenEstimator,
+) -> Option<(Vec<Transition>, CompressionReport)> {
+    if transitions.len() <= keep_recent {
+        return None;
+    }
+
+    let split = transitions.len() - keep_recent;
+    let (old, recent) = transitions.split_at(split);
+    let tokens_before = estimate_tokens(transitions, estimator);
+
+    let summary = summarize(old);
+    let last_old = old.last().expect("old is non-empty: split < len");
+    let synthetic = Transition {
+        id: Uuid::new_v4(),
+        before: old[0].before.clone(),
+        prompt: ClaudePrompt {
+            text: format!("[auto-compressed {} earlier turn(s)]", old.len()),
+            continue_session: false,
+            resume_session_id: None,
+        },
+        execution: ClaudeExecution {
+            prompt: ClaudePrompt::default(),
+            response: summary,
+            session_id: last_old.execution.session_id.clone(),
+            cost: 0.0,
+            duration_ms: 0,
+            model: "compression".to_string(),
+            timestamp: Utc::now(),
+        },
+        after: last_old.after.clone(),
+        recorded_at: Utc::now(),
+        metadata: TransitionMetadata::default(),
+    };
+
+    let mut compacted = Vec::with_capacity(1 + recent.len());
+    compacted.push(synthetic);
+    compacted.extend_from_slice(recent);
+    let tokens_after = estimate_tokens(&compacted, estimator);
+
+    Some((
+        compacted,
+        CompressionReport {
+            transitions_compacted: old.len(),
+            tokens_before,
+            tokens_after,
+        },
+    ))
+}
+
+/// Build a plain-text summary of `transitions`, one bullet per user prompt.
+fn summarize(transitions: &[Transition]) -> String {
+    let mut summary = String::new();
+    for t in transitions {
+        let first_line = t.prompt.text.lines().next().unwrap_or("").trim();
+        summary.push_str("- ");
+        summary.push_str(first_line);
+        summary.push('\n');
+    }
+    summary
+}
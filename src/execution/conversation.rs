@@ -4,28 +4,80 @@
 //! maintains its own history of transitions.
 
 use chrono::{DateTime, Utc};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use uuid::Uuid;
 
 use super::{
     Workspace, WorkspaceError,
-    ClaudePrompt,
-    EnvironmentSnapshot, Transition,
+    ClaudePrompt, StreamEvent, ExecutorError,
+    EnvironmentSnapshot, Transition, TransitionMetadata,
+    blob_store::{self, BlobStore, CasStore},
+    compression::{self, CharsPerTokenEstimator, CompressionReport, TokenEstimator},
     recorder::{TransitionRecorder, RecorderError},
     observer::{PRE_CONVERSATION_SESSION_ID, NO_SESSION_FILE},
 };
 
+/// How long the workspace must go without a new filesystem event before
+/// [`finish_turn`](Conversation::finish_turn) considers it settled enough to
+/// take the `after` snapshot. Mirrors
+/// [`super::environment::ClaudeEnvironment`]'s default settle quiet period.
+const SETTLE_QUIET: Duration = Duration::from_millis(200);
+/// Hard ceiling on total settle time, in case events never stop arriving.
+const SETTLE_TIMEOUT: Duration = Duration::from_secs(5);
+/// Fixed delay used in place of watching when a watcher can't be started at
+/// all (e.g. an unsupported platform or a sandboxed host).
+const SETTLE_FALLBACK_SLEEP: Duration = Duration::from_millis(500);
+
+/// Default fraction of `token_budget` that must be reached before
+/// `send` auto-compresses history.
+const DEFAULT_COMPRESSION_THRESHOLD: f64 = 0.75;
+
+/// How many of the most recent transitions auto-compression always leaves
+/// untouched, so the model keeps verbatim context for what just happened.
+const DEFAULT_COMPRESSION_KEEP_RECENT: usize = 4;
+
+/// How strictly a [`Conversation`] enforces persisting its transitions to
+/// its recorder.
+///
+/// Ordered `Disabled < BestEffort < Required` so that reloading a
+/// conversation can widen the caller-requested policy to whatever was
+/// already in effect (via `Ord::max`) instead of silently downgrading it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Serialize, Deserialize)]
+pub enum RecordingPolicy {
+    /// No recorder is attached; transitions live only in memory.
+    #[default]
+    Disabled,
+    /// Record each transition, but a recorder failure only logs a warning
+    /// and the turn still completes. The original, pre-`RecordingPolicy`
+    /// behavior.
+    BestEffort,
+    /// A recorder failure aborts `send`/`send_streaming` with
+    /// [`ConversationError::RecorderError`] before any conversation state is
+    /// mutated, and the write is durably confirmed (flushed and read back)
+    /// before the turn is considered complete.
+    Required,
+}
+
 /// Serializable representation of a Conversation
 #[derive(Debug, Serialize, Deserialize)]
 struct SavedConversation {
     id: Uuid,
+    /// Transitions with every `FileSnapshot::body` stripped out; rehydrated
+    /// from `blobs` on load. See [`blob_store`].
     transitions: Vec<Transition>,
+    /// Content-addressed file bodies referenced by the transitions above,
+    /// each stored once regardless of how many snapshots share it.
+    #[serde(default)]
+    blobs: BlobStore,
     session_ids: Vec<String>,
     metadata: ConversationMetadata,
     #[serde(default)]
-    recording_enabled: bool,
+    recording_policy: RecordingPolicy,
 }
 
 /// A conversation with Claude that maintains its own history
@@ -47,6 +99,18 @@ pub struct Conversation {
 
     /// Optional recorder for persisting transitions to disk
     recorder: Option<TransitionRecorder>,
+
+    /// How strictly `recorder` failures are enforced; see [`RecordingPolicy`].
+    recording_policy: RecordingPolicy,
+
+    /// Context-window token budget; `None` disables auto-compression.
+    token_budget: Option<usize>,
+
+    /// Fraction of `token_budget` that triggers auto-compression before `send`.
+    compression_threshold: f64,
+
+    /// Pluggable token estimator, defaults to a chars/4 heuristic.
+    estimator: Box<dyn TokenEstimator>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -55,23 +119,97 @@ pub struct ConversationMetadata {
     pub workspace_path: PathBuf,
     pub total_cost_usd: f64,
     pub total_messages: usize,
+    /// Model used by the most recently completed turn, if any.
+    #[serde(default)]
+    pub last_model: Option<String>,
+}
+
+/// A running recursive filesystem watcher plus the channel its events land
+/// on, kept alive only for the duration of one turn's settle wait.
+struct ActiveWatch {
+    _watcher: RecommendedWatcher,
+    events: mpsc::Receiver<notify::Event>,
+}
+
+/// Start watching `workspace` recursively, or return `None` if a watcher
+/// can't be started — [`wait_for_settle`] falls back to a fixed sleep in
+/// that case.
+fn start_watch(workspace: &Path) -> Option<ActiveWatch> {
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+        if let Ok(event) = res {
+            let _ = tx.send(event);
+        }
+    })
+    .ok()?;
+
+    watcher.watch(workspace, RecursiveMode::Recursive).ok()?;
+
+    Some(ActiveWatch { _watcher: watcher, events: rx })
+}
+
+/// Wait for the workspace to settle: drain `watch`'s events until
+/// `SETTLE_QUIET` passes with none arriving, or `SETTLE_TIMEOUT` total has
+/// elapsed, whichever comes first. Returns every distinct path that
+/// changed. Replaces a blind fixed sleep with a real signal, so a turn that
+/// touched no files (or finished writing quickly) doesn't wait around, while
+/// one still mid-write gets the full debounce window.
+fn wait_for_settle(watch: Option<ActiveWatch>) -> Vec<PathBuf> {
+    let Some(watch) = watch else {
+        std::thread::sleep(SETTLE_FALLBACK_SLEEP);
+        return Vec::new();
+    };
+
+    let deadline = Instant::now() + SETTLE_TIMEOUT;
+    let mut changed = std::collections::BTreeSet::new();
+
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            break;
+        }
+        match watch.events.recv_timeout(SETTLE_QUIET.min(remaining)) {
+            Ok(event) => changed.extend(event.paths),
+            Err(RecvTimeoutError::Timeout) => break,
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+    }
+
+    changed.into_iter().collect()
+}
+
+/// Whether `err` looks like Claude rejected a `--resume <session_id>`
+/// because that session is no longer valid (expired, evicted, or otherwise
+/// unknown to Claude), as opposed to some other execution failure. There's
+/// no dedicated `ExecutorError` variant for this — the CLI only ever
+/// surfaces it as an opaque stderr string via `ClaudeFailed` — so this
+/// matches on the wording Claude is known to use rather than the error enum
+/// shape.
+fn is_stale_session_error(err: &ExecutorError) -> bool {
+    let ExecutorError::ClaudeFailed(message) = err else {
+        return false;
+    };
+    let message = message.to_lowercase();
+    message.contains("no conversation found")
+        || (message.contains("session") && (message.contains("not found") || message.contains("invalid") || message.contains("expired")))
 }
 
 impl Conversation {
     /// Create a new conversation in the given workspace
     pub fn new(workspace: Arc<Workspace>) -> Self {
-        Self::new_with_options(workspace, false).expect("record=false cannot fail")
+        Self::new_with_options(workspace, RecordingPolicy::Disabled)
+            .expect("RecordingPolicy::Disabled cannot fail")
     }
 
     /// Create a new conversation with options
     pub fn new_with_options(
         workspace: Arc<Workspace>,
-        record: bool,
+        recording_policy: RecordingPolicy,
     ) -> Result<Self, ConversationError> {
-        let recorder = if record {
-            Some(TransitionRecorder::new(workspace.path())?)
-        } else {
+        let recorder = if recording_policy == RecordingPolicy::Disabled {
             None
+        } else {
+            Some(TransitionRecorder::new(workspace.path())?)
         };
 
         Ok(Self {
@@ -84,16 +222,21 @@ impl Conversation {
                 workspace_path: workspace.path().clone(),
                 total_cost_usd: 0.0,
                 total_messages: 0,
+                last_model: None,
             },
             recorder,
+            recording_policy,
+            token_budget: None,
+            compression_threshold: DEFAULT_COMPRESSION_THRESHOLD,
+            estimator: Box::new(CharsPerTokenEstimator::default()),
         })
     }
 
-    /// Send a message in this conversation
-    pub fn send(&mut self, message: &str) -> Result<Transition, ConversationError> {
-        // Capture before state
-        let before = if self.session_ids.is_empty() {
-            // First message - no session to snapshot
+    /// Capture the environment snapshot a new turn should diff against:
+    /// the pre-conversation sentinel on the very first message, or the
+    /// current workspace state otherwise.
+    fn before_snapshot(&self) -> Result<EnvironmentSnapshot, ConversationError> {
+        Ok(if self.session_ids.is_empty() {
             EnvironmentSnapshot {
                 files: self.workspace.snapshot_files()?,
                 session_file: PathBuf::from(NO_SESSION_FILE),
@@ -102,22 +245,26 @@ impl Conversation {
                 session: None,
             }
         } else {
-            // Continuing - snapshot current state
             self.workspace.snapshot()?
-        };
-
-        // Build prompt with resume_session_id if continuing
-        let prompt = ClaudePrompt {
-            text: message.to_string(),
-            continue_session: false, // Never use the ambiguous continue flag
-            resume_session_id: self.session_ids.last().cloned(),
-        };
-
-        // Execute via workspace
-        let execution = self.workspace.executor.execute(prompt.clone())?;
+        })
+    }
 
-        // Small delay to let file system settle
-        std::thread::sleep(std::time::Duration::from_millis(500));
+    /// Record the outcome of a completed execution as a new `Transition`,
+    /// updating conversation state and the recorder. Shared by `send` and
+    /// `send_streaming`.
+    fn finish_turn(
+        &mut self,
+        before: EnvironmentSnapshot,
+        prompt: ClaudePrompt,
+        execution: super::ClaudeExecution,
+        watch: Option<ActiveWatch>,
+        session_recovered_from: Option<String>,
+    ) -> Result<Transition, ConversationError> {
+        // Wait for a real settle signal instead of blindly sleeping: `watch`
+        // was started before the execution ran, so it sees every filesystem
+        // event produced and returns as soon as things go quiet rather than
+        // always waiting a fixed delay.
+        let changed_paths = wait_for_settle(watch);
 
         // Capture after state with new session ID
         let after = self
@@ -132,20 +279,37 @@ impl Conversation {
             execution: execution.clone(),
             after,
             recorded_at: Utc::now(),
-            metadata: serde_json::json!({
-                "conversation_id": self.id.to_string(),
-            }),
+            metadata: TransitionMetadata {
+                changed_paths,
+                parent: None,
+                session_recovered_from,
+            },
         };
 
+        // Under `Required`, the transition must be durably recorded before
+        // any conversation state is mutated, so a recorder failure leaves
+        // the conversation exactly as it was before this turn.
+        if self.recording_policy == RecordingPolicy::Required {
+            let recorder = self
+                .recorder
+                .as_mut()
+                .expect("Required policy always has a recorder");
+            recorder.record_durable(transition.clone())?;
+        }
+
         // Update conversation state
         self.session_ids.push(execution.session_id);
         self.metadata.total_cost_usd += execution.cost;
         self.metadata.total_messages += 1;
-
-        // Record if recorder is enabled
-        if let Some(ref mut recorder) = self.recorder {
-            if let Err(e) = recorder.record(&transition) {
-                eprintln!("Warning: Failed to record transition: {}", e);
+        self.metadata.last_model = Some(execution.model.clone());
+
+        // `BestEffort` records after state is updated, same as before
+        // `RecordingPolicy` existed: a failure here is only ever a warning.
+        if self.recording_policy == RecordingPolicy::BestEffort {
+            if let Some(ref mut recorder) = self.recorder {
+                if let Err(e) = recorder.record(transition.clone()) {
+                    eprintln!("Warning: Failed to record transition: {}", e);
+                }
             }
         }
 
@@ -154,6 +318,84 @@ impl Conversation {
         Ok(transition)
     }
 
+    /// Send a message in this conversation
+    pub fn send(&mut self, message: &str) -> Result<Transition, ConversationError> {
+        // Auto-compress history if it's grown past the configured budget.
+        self.compress_if_needed();
+
+        let before = self.before_snapshot()?;
+
+        // Build prompt with resume_session_id if continuing
+        let resume_session_id = self.session_ids.last().cloned();
+        let prompt = ClaudePrompt {
+            text: message.to_string(),
+            continue_session: false, // Never use the ambiguous continue flag
+            resume_session_id: resume_session_id.clone(),
+        };
+
+        // Execute via workspace
+        let watch = start_watch(self.workspace.path());
+        let (execution, prompt, session_recovered_from) =
+            match self.workspace.executor.execute(prompt.clone()) {
+                Ok(execution) => (execution, prompt, None),
+                // The prior session ID is no longer accepted by Claude
+                // (expired or evicted) — rather than failing the whole
+                // conversation, transparently restart as a fresh session so
+                // the caller can keep going.
+                Err(e) if resume_session_id.is_some() && is_stale_session_error(&e) => {
+                    let fresh_prompt = ClaudePrompt {
+                        text: message.to_string(),
+                        continue_session: false,
+                        resume_session_id: None,
+                    };
+                    let execution = self.workspace.executor.execute(fresh_prompt.clone())?;
+                    (execution, fresh_prompt, resume_session_id)
+                }
+                Err(e) => return Err(e.into()),
+            };
+
+        self.finish_turn(before, prompt, execution, watch, session_recovered_from)
+    }
+
+    /// Send a message, streaming incremental [`StreamEvent`]s to `on_event`
+    /// as they arrive instead of blocking silently until the whole turn
+    /// completes. Still blocks until the turn finishes and returns the final
+    /// `Transition`, exactly like `send`.
+    ///
+    /// This is the crate's equivalent of an async event stream terminated by
+    /// a final value: there's no tokio/futures dependency anywhere in this
+    /// tree, so rather than bolt on an async runtime for one API, event
+    /// delivery uses the same thread + channel idiom as
+    /// [`super::executor::StreamHandle`] — `on_event` is invoked on the
+    /// calling thread as each event arrives over the channel fed by the
+    /// executor's background reader thread, and the call still returns the
+    /// `Transition` once the turn completes.
+    pub fn send_streaming(
+        &mut self,
+        message: &str,
+        mut on_event: impl FnMut(StreamEvent),
+    ) -> Result<Transition, ConversationError> {
+        // Auto-compress history if it's grown past the configured budget.
+        self.compress_if_needed();
+
+        let before = self.before_snapshot()?;
+
+        let prompt = ClaudePrompt {
+            text: message.to_string(),
+            continue_session: false,
+            resume_session_id: self.session_ids.last().cloned(),
+        };
+
+        let watch = start_watch(self.workspace.path());
+        let (events, handle) = self.workspace.executor.execute_streaming(prompt.clone())?;
+        for event in events {
+            on_event(event);
+        }
+        let execution = handle.join()?;
+
+        self.finish_turn(before, prompt, execution, watch, None)
+    }
+
     /// Get all transitions in this conversation
     pub fn history(&self) -> &[Transition] {
         &self.transitions
@@ -189,6 +431,11 @@ impl Conversation {
         self.recorder.as_ref()
     }
 
+    /// This conversation's recording policy; see [`RecordingPolicy`].
+    pub fn recording_policy(&self) -> RecordingPolicy {
+        self.recording_policy
+    }
+
     /// Get tools used across all transitions
     ///
     /// Note: This currently returns an empty vector because ParsedSession
@@ -208,38 +455,104 @@ impl Conversation {
         result
     }
 
-    /// Save conversation to disk
+    /// Branch a new conversation off this one at the `at`-th transition: the
+    /// fork starts with transitions `[0, at)` and the session ids those
+    /// transitions themselves carry, so its next `send` resumes the session
+    /// active at that point instead of the tip of `self`. `at` is clamped to
+    /// this conversation's length, so forking at or past the end just clones
+    /// the whole history. The fork gets a fresh `id`, its own recorder
+    /// (matching `self`'s [`RecordingPolicy`]), and `metadata.total_cost_usd`/
+    /// `total_messages` recomputed from the retained prefix; it's otherwise
+    /// independent, so sending on it never mutates `self`.
+    pub fn fork(&self, at: usize) -> Result<Conversation, ConversationError> {
+        let at = at.min(self.transitions.len());
+        let transitions = self.transitions[..at].to_vec();
+        // Read session ids straight off the retained transitions rather
+        // than slicing `self.session_ids` positionally: after compaction
+        // (see `compress_if_needed`) one `transitions` entry can be a
+        // synthetic summary standing in for many original `send`s, so the
+        // two vectors aren't guaranteed to stay the same length forever —
+        // deriving from `transitions` itself can't drift out of alignment.
+        let session_ids = transitions
+            .iter()
+            .map(|t| t.execution.session_id.clone())
+            .collect();
+
+        let total_cost_usd = transitions.iter().map(|t| t.execution.cost).sum();
+        let last_model = transitions.last().map(|t| t.execution.model.clone());
+
+        let recorder = if self.recording_policy == RecordingPolicy::Disabled {
+            None
+        } else {
+            Some(TransitionRecorder::new(self.workspace.path())?)
+        };
+
+        Ok(Conversation {
+            id: Uuid::new_v4(),
+            workspace: self.workspace.clone(),
+            metadata: ConversationMetadata {
+                created_at: Utc::now(),
+                workspace_path: self.workspace.path().clone(),
+                total_cost_usd,
+                total_messages: transitions.len(),
+                last_model,
+            },
+            session_ids,
+            transitions,
+            recorder,
+            recording_policy: self.recording_policy,
+            token_budget: self.token_budget,
+            compression_threshold: self.compression_threshold,
+            estimator: Box::new(CharsPerTokenEstimator::default()),
+        })
+    }
+
+    /// Save conversation to disk.
+    ///
+    /// File bodies are factored out into a shared, content-addressed
+    /// `blobs` map keyed by hash before serializing, so a body that's
+    /// identical across many snapshots (the common case for a large,
+    /// mostly-unchanged workspace) is written once instead of once per
+    /// snapshot. Those same bodies are also written into the workspace's
+    /// on-disk [`CasStore`], so another conversation against the same
+    /// workspace reuses them instead of writing its own copy, and so they
+    /// survive independently of any one conversation file.
     pub fn save(&self, path: &std::path::Path) -> Result<(), ConversationError> {
+        let (transitions, blobs) = blob_store::extract_blobs(&self.transitions);
+        CasStore::new(self.workspace.path()).write_all(&blobs)?;
         let saved = SavedConversation {
             id: self.id,
-            transitions: self.transitions.clone(),
+            transitions,
+            blobs,
             session_ids: self.session_ids.clone(),
             metadata: self.metadata.clone(),
-            recording_enabled: self.recorder.is_some(),
+            recording_policy: self.recording_policy,
         };
         let data = serde_json::to_string_pretty(&saved)?;
         std::fs::write(path, data)?;
         Ok(())
     }
 
-    /// Load conversation from disk
+    /// Load conversation from disk, rehydrating file bodies from the saved
+    /// blob store so callers see the same fully-populated snapshots they
+    /// would from a live conversation.
     pub fn load(
         path: &std::path::Path,
         workspace: Arc<Workspace>,
-        record: bool,
+        recording_policy: RecordingPolicy,
     ) -> Result<Self, ConversationError> {
         let data = std::fs::read_to_string(path)?;
-        let saved: SavedConversation = serde_json::from_str(&data)?;
-
-        let record = if record {
-            true
+        let mut saved: SavedConversation = serde_json::from_str(&data)?;
+        blob_store::hydrate(&mut saved.transitions, &saved.blobs);
+
+        // Never silently downgrade: a conversation saved under `Required` or
+        // `BestEffort` stays at least that strict even if the caller asks
+        // for less here.
+        let recording_policy = recording_policy.max(saved.recording_policy);
+        let recorder = if recording_policy == RecordingPolicy::Disabled {
+            None
         } else {
-            saved.recording_enabled
-        };
-        let recorder = if record {
             Some(TransitionRecorder::new(workspace.path())?)
-        } else {
-            None
         };
 
         Ok(Self {
@@ -249,8 +562,121 @@ impl Conversation {
             session_ids: saved.session_ids,
             metadata: saved.metadata,
             recorder,
+            recording_policy,
+            token_budget: None,
+            compression_threshold: DEFAULT_COMPRESSION_THRESHOLD,
+            estimator: Box::new(CharsPerTokenEstimator::default()),
         })
     }
+
+    /// Every blob hash referenced by the transitions saved at `path`,
+    /// without fully rehydrating them. Used by
+    /// [`super::store::ConversationStore::gc_blobs`] to find everything
+    /// still in use across every conversation sharing a workspace's CAS
+    /// before deleting what isn't.
+    pub(crate) fn referenced_blob_hashes(
+        path: &std::path::Path,
+    ) -> Result<std::collections::HashSet<String>, ConversationError> {
+        let data = std::fs::read_to_string(path)?;
+        let saved: SavedConversation = serde_json::from_str(&data)?;
+
+        let mut hashes = std::collections::HashSet::new();
+        for transition in &saved.transitions {
+            for snapshot in [&transition.before, &transition.after] {
+                for file in snapshot.files.values() {
+                    hashes.insert(file.hash.clone());
+                }
+            }
+        }
+        Ok(hashes)
+    }
+
+    /// Save this conversation under `name` through `store`, so it can later
+    /// be found by name instead of by path.
+    pub fn save_named(
+        &self,
+        store: &super::ConversationStore,
+        name: &str,
+    ) -> Result<(), ConversationError> {
+        store.save(name, self)
+    }
+
+    /// Load the conversation stored under `name` in `store` back into a
+    /// live `Conversation` against `workspace`.
+    pub fn load_named(
+        store: &super::ConversationStore,
+        name: &str,
+        workspace: Arc<Workspace>,
+        recording_policy: RecordingPolicy,
+    ) -> Result<Self, ConversationError> {
+        store.load(name, workspace, recording_policy)
+    }
+
+    /// Set a context-window token budget, enabling auto-compression.
+    ///
+    /// `None` (the default) disables auto-compression entirely.
+    pub fn set_token_budget(&mut self, token_budget: Option<usize>) {
+        self.token_budget = token_budget;
+    }
+
+    /// Get the configured token budget, if any.
+    pub fn token_budget(&self) -> Option<usize> {
+        self.token_budget
+    }
+
+    /// Set the fraction of `token_budget` that must be reached before
+    /// `send` auto-compresses history. Defaults to 0.75.
+    pub fn set_compression_threshold(&mut self, threshold: f64) {
+        self.compression_threshold = threshold;
+    }
+
+    /// Get the configured compression threshold.
+    pub fn compression_threshold(&self) -> f64 {
+        self.compression_threshold
+    }
+
+    /// Plug in a custom token estimator, e.g. one backed by the model's real
+    /// tokenizer. Defaults to [`CharsPerTokenEstimator`].
+    pub fn set_token_estimator(&mut self, estimator: Box<dyn TokenEstimator>) {
+        self.estimator = estimator;
+    }
+
+    /// Estimate the token cost of the conversation's current history.
+    pub fn estimated_tokens(&self) -> usize {
+        compression::estimate_tokens(&self.transitions, self.estimator.as_ref())
+    }
+
+    /// Compact history if it has crossed `token_budget * compression_threshold`.
+    ///
+    /// No-op (returns `None`) when no `token_budget` is set, or when usage is
+    /// still under threshold. Called automatically at the start of `send`.
+    pub fn compress_if_needed(&mut self) -> Option<CompressionReport> {
+        let budget = self.token_budget?;
+        let estimated = self.estimated_tokens();
+        if (estimated as f64) < (budget as f64) * self.compression_threshold {
+            return None;
+        }
+
+        let (compacted, report) = compression::compact_transitions(
+            &self.transitions,
+            DEFAULT_COMPRESSION_KEEP_RECENT,
+            self.estimator.as_ref(),
+        )?;
+
+        // `compact_transitions` folds every transition but the most recent
+        // `keep_recent` into one synthetic summary transition carrying the
+        // last folded transition's session id. `session_ids` is pushed in
+        // lockstep with `transitions` (one entry per `send`), so it gets
+        // the same fold to stay index-aligned with `transitions` once a
+        // conversation has compressed.
+        let split = report.transitions_compacted;
+        let mut session_ids = vec![self.session_ids[split - 1].clone()];
+        session_ids.extend_from_slice(&self.session_ids[split..]);
+        self.session_ids = session_ids;
+
+        self.transitions = compacted;
+        Some(report)
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -272,4 +698,7 @@ pub enum ConversationError {
 
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
+
+    #[error("invalid conversation name {0:?}: must be non-empty, not `.` or `..`, and contain no path separators")]
+    InvalidName(String),
 }
@@ -1,16 +1,30 @@
-use std::path::PathBuf;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::{Duration, Instant};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
 use uuid::Uuid;
 use crate::execution::{
     ClaudeExecutor, ClaudePrompt, ExecutorError,
     EnvironmentObserver, EnvironmentSnapshot, ObserverError,
-    TransitionRecorder, Transition, RecorderError,
+    TransitionRecorder, Transition, TransitionMetadata, RecorderError,
 };
 
+/// Default quiet period: how long the workspace must go without a new
+/// create/modify/remove event before it's considered settled.
+const DEFAULT_SETTLE_QUIET: Duration = Duration::from_millis(200);
+/// Hard ceiling on total settle time, in case events never stop arriving.
+const DEFAULT_SETTLE_TIMEOUT: Duration = Duration::from_secs(5);
+/// Fixed delay used in place of watching when a watcher can't be started at all.
+const SETTLE_FALLBACK_SLEEP: Duration = Duration::from_millis(500);
+
 pub struct ClaudeEnvironment {
     executor: ClaudeExecutor,
     observer: EnvironmentObserver,
     recorder: TransitionRecorder,
     workspace: PathBuf,
+    settle_quiet: Duration,
+    settle_timeout: Duration,
 }
 
 impl ClaudeEnvironment {
@@ -18,38 +32,79 @@ impl ClaudeEnvironment {
         let executor = ClaudeExecutor::new(workspace.clone())?;
         let observer = EnvironmentObserver::new(workspace.clone());
         let recorder = TransitionRecorder::new(&workspace)?;
-        
+
         Ok(Self {
             executor,
             observer,
             recorder,
             workspace,
+            settle_quiet: DEFAULT_SETTLE_QUIET,
+            settle_timeout: DEFAULT_SETTLE_TIMEOUT,
         })
     }
-    
+
+    /// Override the quiet period and hard timeout the filesystem-watcher
+    /// settle detection between executing a prompt and taking the
+    /// after-snapshot uses. Falls back to a fixed sleep regardless of these
+    /// values if no watcher could be started for the workspace.
+    pub fn with_watch_settle(mut self, quiet: Duration, timeout: Duration) -> Self {
+        self.settle_quiet = quiet;
+        self.settle_timeout = timeout;
+        self
+    }
+
     /// Start a new session with the given prompt
     pub fn start(&mut self, prompt: &str) -> Result<Transition, EnvironmentError> {
         self.execute_with_options(prompt, false)
     }
-    
+
     /// Continue the current session with the given prompt
     pub fn continue_session(&mut self, prompt: &str) -> Result<Transition, EnvironmentError> {
         self.execute_with_options(prompt, true)
     }
-    
+
     /// Execute with explicit options (legacy method, prefer start/continue)
     pub fn execute(&mut self, prompt: &str) -> Result<Transition, EnvironmentError> {
         self.execute_with_options(prompt, false)
     }
-    
+
+    /// Resume a specific past session by id (`claude --resume <id>`) rather
+    /// than whichever one `continue_session` happens to find most recently
+    /// modified. `session_id` can name any session this workspace has ever
+    /// recorded, not just the current one.
+    pub fn resume(&mut self, session_id: &str, prompt: &str) -> Result<Transition, EnvironmentError> {
+        self.run(prompt, false, Some(session_id.to_string()), None)
+    }
+
+    /// Start a new branch from the state just after `transition_id`
+    /// completed, without disturbing the original line: resumes that
+    /// transition's session and tags the resulting transition's
+    /// `metadata.parent` with its id, so [`TransitionRecorder::tree`] can
+    /// reconstruct the branching history later.
+    pub fn fork(&mut self, transition_id: Uuid, prompt: &str) -> Result<Transition, EnvironmentError> {
+        let parent = self.recorder.load(transition_id)?
+            .ok_or(EnvironmentError::TransitionNotFound(transition_id))?;
+        self.run(prompt, false, Some(parent.execution.session_id.clone()), Some(transition_id))
+    }
+
     fn execute_with_options(
-        &mut self, 
-        prompt: &str, 
+        &mut self,
+        prompt: &str,
         continue_session: bool
+    ) -> Result<Transition, EnvironmentError> {
+        self.run(prompt, continue_session, None, None)
+    }
+
+    fn run(
+        &mut self,
+        prompt: &str,
+        continue_session: bool,
+        resume_session_id: Option<String>,
+        parent: Option<Uuid>,
     ) -> Result<Transition, EnvironmentError> {
         // Capture before state
-        let before = if continue_session {
-            // If continuing, get the most recent session
+        let before = if continue_session || resume_session_id.is_some() {
+            // If continuing or resuming, get the relevant session
             self.observer.snapshot()?
         } else {
             // If starting fresh, create empty before state
@@ -60,21 +115,29 @@ impl ClaudeEnvironment {
                 session: None,  // No session to compare against
             }
         };
-        
+
         // Execute prompt
         let claude_prompt = ClaudePrompt {
             text: prompt.to_string(),
             continue_session,
-            resume_session_id: None,  // TODO: Add method to resume specific sessions
+            resume_session_id,
         };
+
+        // Watch for filesystem activity across the run instead of blindly
+        // sleeping afterward: a watcher started now sees every event the
+        // execution produces, so settling can finish as soon as things go
+        // quiet rather than always waiting the old fixed 500ms.
+        let watch = start_watch(&self.workspace);
+
         let execution = self.executor.execute(claude_prompt.clone())?;
-        
-        // Small delay to let file system settle
-        std::thread::sleep(std::time::Duration::from_millis(500));
-        
+
+        let changed_paths = settle(watch, self.settle_quiet, self.settle_timeout);
+
         // Capture after state using the session ID from execution
         let after = self.observer.snapshot_with_session(&execution.session_id)?;
-        
+
+        let metadata = TransitionMetadata { changed_paths, parent, session_recovered_from: None };
+
         // Create transition
         let transition = Transition {
             id: Uuid::new_v4(),
@@ -83,38 +146,101 @@ impl ClaudeEnvironment {
             execution,
             after,
             recorded_at: chrono::Utc::now(),
-            metadata: serde_json::Value::Null,
+            metadata,
         };
-        
+
         // Record it
         self.recorder.record(transition.clone())?;
-        
+
         Ok(transition)
     }
-    
+
     pub fn history(&self, limit: Option<usize>) -> Result<Vec<Transition>, EnvironmentError> {
         self.recorder.recent(limit)
             .map_err(|e| EnvironmentError::RecorderError(e))
     }
-    
+
     pub fn replay(&self, transition_id: Uuid) -> Result<Option<Transition>, EnvironmentError> {
         self.recorder.load(transition_id)
             .map_err(|e| EnvironmentError::RecorderError(e))
     }
-    
+
+    /// The branching history rooted at `transition_id`: that transition plus
+    /// every one `fork`ed from it, directly or transitively.
+    pub fn tree(&self, transition_id: Uuid) -> Result<Vec<Transition>, EnvironmentError> {
+        self.recorder.tree(transition_id)
+            .map_err(|e| EnvironmentError::RecorderError(e))
+    }
+
     pub fn workspace(&self) -> &PathBuf {
         &self.workspace
     }
 }
 
+/// A running recursive filesystem watcher plus the channel its events land
+/// on. Kept alive only for the duration of one [`settle`] call.
+struct ActiveWatch {
+    _watcher: RecommendedWatcher,
+    events: mpsc::Receiver<notify::Event>,
+}
+
+/// Start watching `workspace` recursively, or return `None` if a watcher
+/// can't be started (unsupported platform, sandbox restrictions, etc.) —
+/// [`settle`] falls back to a fixed sleep in that case.
+fn start_watch(workspace: &Path) -> Option<ActiveWatch> {
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+        if let Ok(event) = res {
+            let _ = tx.send(event);
+        }
+    })
+    .ok()?;
+
+    watcher.watch(workspace, RecursiveMode::Recursive).ok()?;
+
+    Some(ActiveWatch { _watcher: watcher, events: rx })
+}
+
+/// Wait for the workspace to settle: drain `watch`'s events until `quiet`
+/// passes with none arriving, or `timeout` total has elapsed, whichever
+/// comes first. Returns every distinct path that changed, deduplicated and
+/// sorted. Falls back to a fixed sleep (matching the old fixed-delay
+/// behavior) and an empty path set when no watcher could be started.
+fn settle(watch: Option<ActiveWatch>, quiet: Duration, timeout: Duration) -> Vec<PathBuf> {
+    let Some(watch) = watch else {
+        std::thread::sleep(SETTLE_FALLBACK_SLEEP);
+        return Vec::new();
+    };
+
+    let deadline = Instant::now() + timeout;
+    let mut changed = BTreeSet::new();
+
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            break;
+        }
+        match watch.events.recv_timeout(quiet.min(remaining)) {
+            Ok(event) => changed.extend(event.paths),
+            Err(RecvTimeoutError::Timeout) => break,
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+    }
+
+    changed.into_iter().collect()
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum EnvironmentError {
     #[error("Executor error: {0}")]
     ExecutorError(#[from] ExecutorError),
-    
+
     #[error("Observer error: {0}")]
     ObserverError(#[from] ObserverError),
-    
+
     #[error("Recorder error: {0}")]
     RecorderError(#[from] RecorderError),
-}
\ No newline at end of file
+
+    #[error("No recorded transition with id {0}")]
+    TransitionNotFound(Uuid),
+}
@@ -0,0 +1,411 @@
+//! Batch evaluation harness: run many prompts across isolated workspaces
+//! and aggregate the results.
+//!
+//! A [`Suite`] of [`Case`]s is handed to a [`Runner`], which provisions a
+//! fresh, temp-dir-backed [`Workspace`] per case, executes it through a
+//! [`Conversation`], and folds the resulting [`Transition`] into a
+//! [`Report`]. Cases run concurrently under a configurable parallelism cap
+//! and in an order shuffled from a seed, so ordering-dependent flakiness
+//! surfaces reproducibly instead of depending on thread-scheduling luck.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::execution::{Conversation, Transition, Workspace};
+
+/// A check run against a case's resulting [`Transition`] after execution.
+/// Returns `Err(message)` describing the failure, or `Ok(())` if it passes.
+pub type Assertion = Box<dyn Fn(&Transition) -> Result<(), String> + Send + Sync>;
+
+/// A single prompt to execute in its own isolated workspace.
+pub struct Case {
+    pub name: String,
+    pub prompt: String,
+    /// Files to seed the workspace with before executing, keyed by path
+    /// relative to the workspace root.
+    pub setup_files: HashMap<PathBuf, String>,
+    /// Tool names the resulting transition is expected to have used; an
+    /// unused expected tool fails the case.
+    pub expected_tools: Vec<String>,
+    /// Extra pass/fail checks run (in order, short-circuiting) against the
+    /// resulting transition.
+    pub assertions: Vec<Assertion>,
+}
+
+impl Case {
+    pub fn new(name: impl Into<String>, prompt: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            prompt: prompt.into(),
+            setup_files: HashMap::new(),
+            expected_tools: Vec::new(),
+            assertions: Vec::new(),
+        }
+    }
+
+    pub fn with_setup_file(mut self, path: impl Into<PathBuf>, contents: impl Into<String>) -> Self {
+        self.setup_files.insert(path.into(), contents.into());
+        self
+    }
+
+    pub fn with_expected_tool(mut self, tool: impl Into<String>) -> Self {
+        self.expected_tools.push(tool.into());
+        self
+    }
+
+    pub fn with_assertion(
+        mut self,
+        assertion: impl Fn(&Transition) -> Result<(), String> + Send + Sync + 'static,
+    ) -> Self {
+        self.assertions.push(Box::new(assertion));
+        self
+    }
+}
+
+/// A named collection of [`Case`]s to run together.
+#[derive(Default)]
+pub struct Suite {
+    pub name: String,
+    pub cases: Vec<Case>,
+}
+
+impl Suite {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            cases: Vec::new(),
+        }
+    }
+
+    pub fn with_case(mut self, case: Case) -> Self {
+        self.cases.push(case);
+        self
+    }
+}
+
+/// Outcome of a single case.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CaseResult {
+    pub name: String,
+    pub passed: bool,
+    pub failure: Option<String>,
+    pub cost_usd: f64,
+    pub duration_ms: u64,
+    pub tools_used: Vec<String>,
+    pub has_tool_errors: bool,
+}
+
+impl CaseResult {
+    fn failed(name: String, failure: String, elapsed: Duration) -> Self {
+        Self {
+            name,
+            passed: false,
+            failure: Some(failure),
+            cost_usd: 0.0,
+            duration_ms: elapsed.as_millis() as u64,
+            tools_used: Vec::new(),
+            has_tool_errors: false,
+        }
+    }
+}
+
+/// Aggregated outcome of a [`Runner::run`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Report {
+    pub suite_name: String,
+    pub total: usize,
+    pub passed: usize,
+    pub failed: usize,
+    pub total_cost_usd: f64,
+    pub duration_ms: u64,
+    pub cases: Vec<CaseResult>,
+}
+
+impl Report {
+    /// One-line human-readable summary, e.g. for CI logs.
+    pub fn summary(&self) -> String {
+        format!(
+            "{}: {}/{} passed, ${:.4} total cost, {}ms wall-clock",
+            self.suite_name, self.passed, self.total, self.total_cost_usd, self.duration_ms
+        )
+    }
+
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+/// Runs a [`Suite`] across isolated, temp-dir-backed workspaces with
+/// bounded concurrency and deterministic-but-randomized case ordering.
+pub struct Runner {
+    parallelism: usize,
+    seed: u64,
+    skip_permissions: bool,
+}
+
+impl Default for Runner {
+    fn default() -> Self {
+        Self {
+            parallelism: 4,
+            seed: 0,
+            skip_permissions: true,
+        }
+    }
+}
+
+impl Runner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cap how many cases run at once. Clamped to at least 1.
+    pub fn with_parallelism(mut self, parallelism: usize) -> Self {
+        self.parallelism = parallelism.max(1);
+        self
+    }
+
+    /// Seed the deterministic shuffle used to order cases before dispatch.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Whether to pass `--dangerously-skip-permissions` to each case's
+    /// workspace. Defaults to `true`, since a harness run isn't interactive.
+    pub fn with_skip_permissions(mut self, skip: bool) -> Self {
+        self.skip_permissions = skip;
+        self
+    }
+
+    /// Execute every case in `suite`, returning the aggregated [`Report`].
+    pub fn run(&self, suite: Suite) -> Report {
+        let start = Instant::now();
+        let Suite { name, cases } = suite;
+
+        let order = shuffled_indices(cases.len(), self.seed);
+        let cases = Arc::new(cases);
+        let order = Arc::new(order);
+        let next_index = Arc::new(Mutex::new(0usize));
+        let results = Mutex::new(Vec::with_capacity(cases.len()));
+        let worker_count = self.parallelism.min(cases.len().max(1));
+
+        std::thread::scope(|scope| {
+            for _ in 0..worker_count {
+                let cases = Arc::clone(&cases);
+                let order = Arc::clone(&order);
+                let next_index = Arc::clone(&next_index);
+                let results = &results;
+                let skip_permissions = self.skip_permissions;
+
+                scope.spawn(move || loop {
+                    let index = {
+                        let mut next = next_index.lock().unwrap();
+                        if *next >= order.len() {
+                            break;
+                        }
+                        let i = *next;
+                        *next += 1;
+                        i
+                    };
+                    let case = &cases[order[index]];
+                    let result = run_case(case, skip_permissions);
+                    results.lock().unwrap().push(result);
+                });
+            }
+        });
+
+        let mut cases = results.into_inner().unwrap();
+        cases.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let passed = cases.iter().filter(|c| c.passed).count();
+        let total = cases.len();
+        let total_cost_usd = cases.iter().map(|c| c.cost_usd).sum();
+
+        Report {
+            suite_name: name,
+            total,
+            passed,
+            failed: total - passed,
+            total_cost_usd,
+            duration_ms: start.elapsed().as_millis() as u64,
+            cases,
+        }
+    }
+}
+
+/// Provision a fresh workspace, seed it, execute `case.prompt` through a new
+/// `Conversation`, and evaluate its expectations/assertions.
+fn run_case(case: &Case, skip_permissions: bool) -> CaseResult {
+    let start = Instant::now();
+    let workspace_path = std::env::temp_dir().join(format!("claude-sdk-eval-{}", Uuid::new_v4()));
+
+    let mut workspace = match Workspace::new(workspace_path.clone()) {
+        Ok(workspace) => workspace,
+        Err(e) => {
+            return CaseResult::failed(
+                case.name.clone(),
+                format!("failed to provision workspace: {e}"),
+                start.elapsed(),
+            )
+        }
+    };
+    workspace.set_skip_permissions(skip_permissions);
+
+    for (path, contents) in &case.setup_files {
+        let full_path = workspace_path.join(path);
+        if let Some(parent) = full_path.parent() {
+            if let Err(e) = fs::create_dir_all(parent) {
+                return CaseResult::failed(
+                    case.name.clone(),
+                    format!("failed to create setup directory {}: {e}", parent.display()),
+                    start.elapsed(),
+                );
+            }
+        }
+        if let Err(e) = fs::write(&full_path, contents) {
+            return CaseResult::failed(
+                case.name.clone(),
+                format!("failed to write setup file {}: {e}", path.display()),
+                start.elapsed(),
+            );
+        }
+    }
+
+    let mut conversation = Conversation::new(Arc::new(workspace));
+    let transition = match conversation.send(&case.prompt) {
+        Ok(transition) => transition,
+        Err(e) => {
+            let _ = fs::remove_dir_all(&workspace_path);
+            return CaseResult::failed(
+                case.name.clone(),
+                format!("execution failed: {e}"),
+                start.elapsed(),
+            );
+        }
+    };
+
+    let tools_used = transition.tools_used();
+    let mut failure = case
+        .expected_tools
+        .iter()
+        .find(|tool| !tools_used.contains(tool))
+        .map(|tool| format!("expected tool `{tool}` was not used"));
+
+    if failure.is_none() {
+        failure = case
+            .assertions
+            .iter()
+            .find_map(|assertion| assertion(&transition).err());
+    }
+
+    let _ = fs::remove_dir_all(&workspace_path);
+
+    CaseResult {
+        name: case.name.clone(),
+        passed: failure.is_none(),
+        failure,
+        cost_usd: transition.execution.cost,
+        duration_ms: start.elapsed().as_millis() as u64,
+        tools_used,
+        has_tool_errors: transition.has_tool_errors(),
+    }
+}
+
+/// Minimal splitmix64 PRNG, used only to deterministically reorder cases
+/// from a seed so ordering-dependent flakiness surfaces reproducibly rather
+/// than depending on whatever order threads happen to claim work in.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    }
+
+    fn next_below(&mut self, bound: usize) -> usize {
+        if bound == 0 {
+            0
+        } else {
+            (self.next_u64() % bound as u64) as usize
+        }
+    }
+}
+
+/// Fisher-Yates shuffle of `0..len`, deterministic for a given `seed`.
+fn shuffled_indices(len: usize, seed: u64) -> Vec<usize> {
+    let mut indices: Vec<usize> = (0..len).collect();
+    let mut rng = SplitMix64::new(seed);
+    for i in (1..len).rev() {
+        let j = rng.next_below(i + 1);
+        indices.swap(i, j);
+    }
+    indices
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shuffled_indices_is_a_permutation_of_the_full_range() {
+        let mut order = shuffled_indices(10, 42);
+        order.sort();
+        assert_eq!(order, (0..10).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn shuffled_indices_is_deterministic_for_a_given_seed() {
+        assert_eq!(shuffled_indices(20, 7), shuffled_indices(20, 7));
+    }
+
+    #[test]
+    fn different_seeds_usually_produce_different_orders() {
+        // Not a mathematical guarantee, but with 20 elements a collision
+        // across these two particular seeds would indicate a broken RNG.
+        assert_ne!(shuffled_indices(20, 1), shuffled_indices(20, 2));
+    }
+
+    #[test]
+    fn shuffled_indices_handles_zero_and_one_length_suites() {
+        assert_eq!(shuffled_indices(0, 123), Vec::<usize>::new());
+        assert_eq!(shuffled_indices(1, 123), vec![0]);
+    }
+
+    #[test]
+    fn next_below_zero_bound_never_panics_and_returns_zero() {
+        let mut rng = SplitMix64::new(1);
+        assert_eq!(rng.next_below(0), 0);
+    }
+
+    #[test]
+    fn next_below_stays_within_bound() {
+        let mut rng = SplitMix64::new(99);
+        for _ in 0..1000 {
+            assert!(rng.next_below(7) < 7);
+        }
+    }
+
+    #[test]
+    fn runner_report_for_an_empty_suite_has_zero_counts() {
+        // Exercising the concurrent path (and the name ordering of
+        // `Report::cases`) needs a real Claude execution, so this only
+        // checks how `Runner::run` aggregates a suite with no cases.
+        let report = Runner::new().with_seed(5).run(Suite::new("empty"));
+        assert_eq!(report.total, 0);
+        assert_eq!(report.passed, 0);
+        assert_eq!(report.failed, 0);
+        assert_eq!(report.cases.len(), 0);
+    }
+}
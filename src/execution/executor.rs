@@ -1,7 +1,118 @@
-use std::process::Command;
+use std::collections::HashMap;
+use std::process::{Child, Command, Stdio};
+use std::io::{BufRead, BufReader, Read};
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
+use crate::execution::mcp::{self, ToolServerHandle, ToolServerSpec};
+use crate::execution::observer::EnvironmentObserver;
+use crate::execution::permissions::{Capability, PermissionDecision, ToolRequest};
+use std::sync::Mutex;
+
+/// How often [`ClaudeExecutor::execute_with_cancel`] polls the child for
+/// exit and checks the timeout/cancellation token while it runs.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// How long a timed-out or cancelled child is given to exit after SIGTERM
+/// before it's escalated to SIGKILL.
+const TERM_GRACE_PERIOD: Duration = Duration::from_secs(2);
+
+/// A shared, cheaply-cloneable flag for cooperatively cancelling an
+/// in-flight [`ClaudeExecutor::execute_with_cancel`] call from another
+/// thread, e.g. in response to a user abort.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self(Arc::new(AtomicBool::new(false)))
+    }
+
+    /// Request cancellation. Safe to call from any thread, any number of
+    /// times.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+/// Send SIGTERM, wait up to [`TERM_GRACE_PERIOD`] for the child to exit on
+/// its own, then SIGKILL if it's still alive. Mirrors the spawn/wait/kill
+/// lifecycle of a typical remote-process client's abort path.
+fn terminate_then_kill(child: &mut Child) {
+    #[cfg(unix)]
+    {
+        // SAFETY: `child.id()` is a live PID for the duration of this call
+        // since `child` is exclusively borrowed and not yet reaped.
+        unsafe {
+            libc::kill(child.id() as libc::pid_t, libc::SIGTERM);
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = child.kill();
+    }
+
+    let deadline = Instant::now() + TERM_GRACE_PERIOD;
+    loop {
+        if matches!(child.try_wait(), Ok(Some(_))) {
+            return;
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            return;
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Like [`terminate_then_kill`], but signals the whole process group rather
+/// than just the child itself, so a `claude` run's own subprocesses (e.g. a
+/// `Bash` tool call still running) are terminated along with it. Requires
+/// the child to have been spawned as its own process group leader (see
+/// [`ClaudeExecutor::execute_streaming`]'s `process_group(0)`); falls back to
+/// signaling just the child on non-unix platforms.
+fn terminate_then_kill_group(child: &mut Child) {
+    #[cfg(unix)]
+    {
+        // SAFETY: `child.id()` is a live PID for the duration of this call
+        // since `child` is exclusively borrowed and not yet reaped; the
+        // negated PID targets the whole process group it leads.
+        unsafe {
+            libc::kill(-(child.id() as libc::pid_t), libc::SIGTERM);
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = child.kill();
+    }
+
+    let deadline = Instant::now() + TERM_GRACE_PERIOD;
+    loop {
+        if matches!(child.try_wait(), Ok(Some(_))) {
+            return;
+        }
+        if Instant::now() >= deadline {
+            #[cfg(unix)]
+            unsafe {
+                libc::kill(-(child.id() as libc::pid_t), libc::SIGKILL);
+            }
+            #[cfg(not(unix))]
+            let _ = child.kill();
+            let _ = child.wait();
+            return;
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ClaudePrompt {
@@ -35,13 +146,144 @@ pub struct ClaudeExecution {
 /// Default tools that Claude Code has access to
 const DEFAULT_ALLOWED_TOOLS: &str = "Task,Bash,Glob,Grep,LS,Read,Edit,MultiEdit,Write,NotebookRead,NotebookEdit,WebFetch,TodoRead,TodoWrite,WebSearch";
 
+/// Name of the MCP config file materialized into the workspace directory
+/// when one or more tool servers are registered.
+const MCP_CONFIG_FILENAME: &str = ".claude-sdk-mcp-config.json";
+
+/// Incremental event emitted while streaming a `claude --output-format stream-json` run.
+///
+/// One event is produced per NDJSON line on the child's stdout. Unknown
+/// `type` values in the raw stream are skipped rather than surfaced here.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum StreamEvent {
+    /// Partial or complete assistant text for the turn.
+    #[serde(rename = "assistant_text")]
+    AssistantText { text: String },
+
+    /// The model started invoking a tool.
+    #[serde(rename = "tool_use_started")]
+    ToolUseStarted {
+        id: String,
+        name: String,
+        input: serde_json::Value,
+    },
+
+    /// A tool call finished and returned a result.
+    #[serde(rename = "tool_result")]
+    ToolResult {
+        tool_use_id: String,
+        content: String,
+        #[serde(default)]
+        is_error: bool,
+    },
+
+    /// A file on disk was created or modified by a tool call.
+    #[serde(rename = "file_edited")]
+    FileEdited { path: String },
+
+    /// Token counts for the turn so far, updated as the response streams in.
+    #[serde(rename = "token_usage")]
+    TokenUsage {
+        input_tokens: u64,
+        output_tokens: u64,
+    },
+
+    /// Terminal event carrying the final response and billing info.
+    #[serde(rename = "result")]
+    FinalResult {
+        session_id: String,
+        cost_usd: f64,
+        #[serde(default)]
+        result: String,
+        #[serde(default)]
+        model: Option<String>,
+    },
+}
+
+/// Raw shape of a single NDJSON line from `--output-format stream-json`.
+/// Deserialized loosely so unrecognized `type`s can be skipped instead of
+/// aborting the whole stream.
+#[derive(Debug, Deserialize)]
+struct RawStreamLine {
+    #[serde(rename = "type")]
+    event_type: String,
+    #[serde(default)]
+    text: Option<String>,
+    #[serde(default)]
+    id: Option<String>,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    input: Option<serde_json::Value>,
+    #[serde(default)]
+    tool_use_id: Option<String>,
+    #[serde(default)]
+    content: Option<String>,
+    #[serde(default)]
+    is_error: Option<bool>,
+    #[serde(default)]
+    path: Option<String>,
+    #[serde(default)]
+    input_tokens: Option<u64>,
+    #[serde(default)]
+    output_tokens: Option<u64>,
+    #[serde(default)]
+    session_id: Option<String>,
+    #[serde(default)]
+    cost_usd: Option<f64>,
+    #[serde(default)]
+    result: Option<String>,
+    #[serde(default)]
+    model: Option<String>,
+}
+
+impl RawStreamLine {
+    fn into_event(self) -> Option<StreamEvent> {
+        match self.event_type.as_str() {
+            "assistant_text" => Some(StreamEvent::AssistantText { text: self.text? }),
+            "tool_use_started" => Some(StreamEvent::ToolUseStarted {
+                id: self.id?,
+                name: self.name?,
+                input: self.input.unwrap_or(serde_json::Value::Null),
+            }),
+            "tool_result" => Some(StreamEvent::ToolResult {
+                tool_use_id: self.tool_use_id?,
+                content: self.content.unwrap_or_default(),
+                is_error: self.is_error.unwrap_or(false),
+            }),
+            "file_edited" => Some(StreamEvent::FileEdited { path: self.path? }),
+            "token_usage" => Some(StreamEvent::TokenUsage {
+                input_tokens: self.input_tokens.unwrap_or(0),
+                output_tokens: self.output_tokens.unwrap_or(0),
+            }),
+            "result" => Some(StreamEvent::FinalResult {
+                session_id: self.session_id?,
+                cost_usd: self.cost_usd.unwrap_or(0.0),
+                result: self.result.unwrap_or_default(),
+                model: self.model,
+            }),
+            // Unknown event types (e.g. future CLI additions) are skipped.
+            _ => None,
+        }
+    }
+}
+
+/// Consulted when the model attempts a tool [`Capability::is_covered`]
+/// doesn't explicitly allow or deny.
+type PermissionHandler = dyn FnMut(&ToolRequest) -> PermissionDecision + Send;
+
 pub struct ClaudeExecutor {
     claude_binary: PathBuf,
     working_directory: PathBuf,
-    allowed_tools: Option<String>,
-    disallowed_tools: Option<String>,
+    permission_profile: Arc<Mutex<Capability>>,
+    permission_handler: Option<Arc<Mutex<PermissionHandler>>>,
     skip_permissions: bool,
     model: Option<String>,
+    tool_servers: Vec<ToolServerHandle>,
+    /// Maximum time `execute`/`execute_with_cancel` will let the `claude`
+    /// child run before terminating it. `None` (the default) never times out.
+    timeout: Option<Duration>,
 }
 
 impl ClaudeExecutor {
@@ -49,27 +291,128 @@ impl ClaudeExecutor {
         // Find claude binary
         let claude_binary = which::which("claude")
             .map_err(|_| ExecutorError::ClaudeNotFound)?;
-        
+
         Ok(Self {
             claude_binary,
             working_directory,
-            allowed_tools: None,
-            disallowed_tools: None,
+            permission_profile: Arc::new(Mutex::new(Capability::default())),
+            permission_handler: None,
             skip_permissions: false,  // Don't skip by default
             model: None,  // Use Claude's default model
+            tool_servers: Vec::new(),
+            timeout: None,
         })
     }
-    
-    /// Set allowed tools (e.g., "Read,Write" or "Bash(npm install)" or "*")
+
+    /// Register an interactive permission handler, consulted whenever the
+    /// model attempts a tool the static profile doesn't explicitly cover.
+    /// `AllowAndRemember`/`DenyAndRemember` fold a matching scope into the
+    /// profile so later turns (and, for `execute_streaming`, later tool
+    /// calls observed from the stream) don't re-prompt for it.
+    ///
+    /// Because the handler only sees tool calls the CLI has already
+    /// reported over `--output-format stream-json`, it can't retroactively
+    /// stop a call the CLI already dispatched in the same turn — remembered
+    /// decisions take effect starting with the next tool call or turn. Only
+    /// consulted by `execute_streaming`; `execute`'s plain JSON output
+    /// carries no per-tool-call events to hook.
+    pub fn set_permission_handler(
+        &mut self,
+        handler: impl FnMut(&ToolRequest) -> PermissionDecision + Send + 'static,
+    ) {
+        self.permission_handler = Some(Arc::new(Mutex::new(handler)));
+    }
+
+    /// Bound how long `execute`/`execute_with_cancel` let the `claude`
+    /// child run before it's terminated with `ExecutorError::Timeout`.
+    /// `None` (the default) never times out.
+    pub fn set_timeout(&mut self, timeout: Option<Duration>) {
+        self.timeout = timeout;
+    }
+
+    /// Register an external tool server (MCP-style): spawn `command` with
+    /// `args`/`env`, perform the `initialize`/`tools/list` JSON-RPC
+    /// handshake to discover what it exposes, and keep it registered so its
+    /// config is passed to every subsequent `claude` invocation via
+    /// `--mcp-config`. Returns the tool names it advertised, so callers can
+    /// fold them into a [`Capability`] via `set_permission_profile`.
+    ///
+    /// The probe process spawned here is independent of the one Claude
+    /// itself launches from the materialized MCP config; it exists purely
+    /// for discovery and is terminated when this executor (or its
+    /// `Workspace`) is dropped.
+    pub fn add_tool_server(
+        &mut self,
+        name: impl Into<String>,
+        command: impl Into<String>,
+        args: Vec<String>,
+        env: HashMap<String, String>,
+    ) -> Result<Vec<String>, ExecutorError> {
+        let spec = ToolServerSpec::new(name, command, args, env);
+        let handle = ToolServerHandle::spawn(spec)?;
+        let tools = handle.tools().to_vec();
+        self.tool_servers.push(handle);
+        Ok(tools)
+    }
+
+    /// Tool servers registered via [`ClaudeExecutor::add_tool_server`].
+    pub fn tool_servers(&self) -> &[ToolServerHandle] {
+        &self.tool_servers
+    }
+
+    /// Write the registered tool servers' config to `{working_directory}/
+    /// {MCP_CONFIG_FILENAME}` in the shape Claude's `--mcp-config` flag
+    /// expects, returning its path if any servers are registered.
+    fn materialize_mcp_config(&self) -> Result<Option<PathBuf>, ExecutorError> {
+        if self.tool_servers.is_empty() {
+            return Ok(None);
+        }
+        let specs: Vec<ToolServerSpec> = self
+            .tool_servers
+            .iter()
+            .map(|handle| handle.spec().clone())
+            .collect();
+        let path = self.working_directory.join(MCP_CONFIG_FILENAME);
+        mcp::write_mcp_config(&path, &specs)?;
+        Ok(Some(path))
+    }
+
+    /// Set allowed tools (e.g., "Read,Write" or "Bash(npm install)" or "*").
+    /// Equivalent to `set_permission_profile` with a single-permission
+    /// capability built from this list plus the current profile's deny
+    /// scopes; the current profile's allow scopes are replaced.
     pub fn set_allowed_tools(&mut self, tools: Option<String>) {
-        self.allowed_tools = tools;
+        let (_, disallowed) = self.permission_profile.lock().unwrap().to_cli_flags();
+        *self.permission_profile.lock().unwrap() =
+            Capability::from_tool_lists(tools.as_deref(), disallowed.as_deref());
     }
-    
-    /// Set disallowed tools (e.g., "Bash(rm -rf)" or "Write")
+
+    /// Set disallowed tools (e.g., "Bash(rm -rf)" or "Write"). Equivalent to
+    /// `set_permission_profile` with a single-permission capability built
+    /// from this list plus the current profile's allow scopes; the current
+    /// profile's deny scopes are replaced.
     pub fn set_disallowed_tools(&mut self, tools: Option<String>) {
-        self.disallowed_tools = tools;
+        let (allowed, _) = self.permission_profile.lock().unwrap().to_cli_flags();
+        *self.permission_profile.lock().unwrap() =
+            Capability::from_tool_lists(allowed.as_deref(), tools.as_deref());
     }
-    
+
+    /// Replace the structured permission profile wholesale.
+    pub fn set_permission_profile(&mut self, profile: Capability) {
+        *self.permission_profile.lock().unwrap() = profile;
+    }
+
+    /// Access the current permission profile for introspection/auditing.
+    pub fn permission_profile(&self) -> Capability {
+        self.permission_profile.lock().unwrap().clone()
+    }
+
+    /// Resolve the `--allowedTools`/`--disallowedTools` flag values to pass
+    /// to the CLI from the structured permission profile.
+    fn resolve_tool_flags(&self) -> (Option<String>, Option<String>) {
+        self.permission_profile.lock().unwrap().to_cli_flags()
+    }
+
     /// Enable dangerous mode that skips all permission checks
     /// This should only be used in tests or when explicitly requested
     pub fn set_skip_permissions(&mut self, skip: bool) {
@@ -83,8 +426,24 @@ impl ClaudeExecutor {
     }
     
     pub fn execute(&self, prompt: ClaudePrompt) -> Result<ClaudeExecution, ExecutorError> {
+        self.execute_with_cancel(prompt, None)
+    }
+
+    /// Like [`ClaudeExecutor::execute`], but also honors `cancel` if given:
+    /// the child is polled (rather than blocked on) so a `cancel.cancel()`
+    /// from another thread, or the configured `timeout` elapsing, aborts the
+    /// run instead of blocking the caller forever. Either case sends
+    /// SIGTERM, waits [`TERM_GRACE_PERIOD`] for a clean exit, escalates to
+    /// SIGKILL, then drains the child's stdout/stderr and returns
+    /// `ExecutorError::Timeout`/`ExecutorError::Cancelled`; the drained
+    /// output is discarded, since neither error variant carries it.
+    pub fn execute_with_cancel(
+        &self,
+        prompt: ClaudePrompt,
+        cancel: Option<&CancellationToken>,
+    ) -> Result<ClaudeExecution, ExecutorError> {
         let start = std::time::Instant::now();
-        
+
         // Build command
         let mut cmd = Command::new(&self.claude_binary);
         cmd.current_dir(&self.working_directory);  // Claude tracks sessions per directory
@@ -108,37 +467,93 @@ impl ClaudeExecutor {
         if self.skip_permissions {
             // Explicitly skip permissions (for tests)
             cmd.arg("--dangerously-skip-permissions");
-        } else if self.allowed_tools.is_some() || self.disallowed_tools.is_some() {
-            // Use explicit permissions if set
-            if let Some(ref allowed) = self.allowed_tools {
-                cmd.arg("--allowedTools").arg(allowed);
-            }
-            if let Some(ref disallowed) = self.disallowed_tools {
-                cmd.arg("--disallowedTools").arg(disallowed);
-            }
         } else {
-            // Default: use standard Claude Code tools
-            cmd.arg("--allowedTools").arg(DEFAULT_ALLOWED_TOOLS);
+            let (allowed, disallowed) = self.resolve_tool_flags();
+            if allowed.is_some() || disallowed.is_some() {
+                if let Some(allowed) = allowed {
+                    cmd.arg("--allowedTools").arg(allowed);
+                }
+                if let Some(disallowed) = disallowed {
+                    cmd.arg("--disallowedTools").arg(disallowed);
+                }
+            } else {
+                // Default: use standard Claude Code tools
+                cmd.arg("--allowedTools").arg(DEFAULT_ALLOWED_TOOLS);
+            }
         }
-        
+
+        if let Some(mcp_config) = self.materialize_mcp_config()? {
+            cmd.arg("--mcp-config").arg(mcp_config);
+        }
+
         // -p must come right before the prompt text
         cmd.arg("-p");
         cmd.arg(&prompt.text);
-        
-        // Execute
-        let output = cmd.output()
+
+        cmd.stdout(Stdio::piped());
+        cmd.stderr(Stdio::piped());
+
+        // Execute, polling instead of blocking so `timeout`/`cancel` can
+        // abort a hung child instead of wedging the caller forever. Stdout
+        // and stderr are drained on background threads the whole time the
+        // child runs, not just after it exits, so a chatty child can't fill
+        // its pipe buffer and deadlock against our poll loop.
+        let mut child = cmd.spawn()
             .map_err(|e| ExecutorError::ExecutionFailed(e.to_string()))?;
-            
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(ExecutorError::ClaudeFailed(stderr.to_string()));
+
+        let mut stdout_pipe = child.stdout.take();
+        let mut stderr_pipe = child.stderr.take();
+        let stdout_reader = std::thread::spawn(move || {
+            let mut buf = String::new();
+            if let Some(ref mut s) = stdout_pipe {
+                let _ = s.read_to_string(&mut buf);
+            }
+            buf
+        });
+        let stderr_reader = std::thread::spawn(move || {
+            let mut buf = String::new();
+            if let Some(ref mut s) = stderr_pipe {
+                let _ = s.read_to_string(&mut buf);
+            }
+            buf
+        });
+
+        let deadline = self.timeout.map(|t| start + t);
+        let status = loop {
+            if let Some(status) = child.try_wait()
+                .map_err(|e| ExecutorError::ExecutionFailed(e.to_string()))?
+            {
+                break status;
+            }
+
+            let timed_out = deadline.is_some_and(|d| Instant::now() >= d);
+            let cancelled = cancel.is_some_and(|c| c.is_cancelled());
+
+            if timed_out || cancelled {
+                terminate_then_kill(&mut child);
+                let _ = stdout_reader.join();
+                let _ = stderr_reader.join();
+                return Err(if cancelled {
+                    ExecutorError::Cancelled
+                } else {
+                    ExecutorError::Timeout(self.timeout.unwrap_or_default())
+                });
+            }
+
+            std::thread::sleep(POLL_INTERVAL);
+        };
+
+        let stdout_buf = stdout_reader.join().unwrap_or_default();
+        let stderr_buf = stderr_reader.join().unwrap_or_default();
+
+        if !status.success() {
+            return Err(ExecutorError::ClaudeFailed(stderr_buf));
         }
-        
+
         // Parse JSON response
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let response: ClaudeJsonResponse = serde_json::from_str(&stdout)
+        let response: ClaudeJsonResponse = serde_json::from_str(&stdout_buf)
             .map_err(|e| ExecutorError::ParseError(e.to_string()))?;
-            
+
         Ok(ClaudeExecution {
             prompt,
             response: response.result.clone(),
@@ -149,6 +564,216 @@ impl ClaudeExecutor {
             timestamp: Utc::now(),
         })
     }
+
+    /// Execute a prompt with `--output-format stream-json`, returning a
+    /// channel of incremental [`StreamEvent`]s plus a [`StreamHandle`] that
+    /// can kill the run early and resolves to the assembled
+    /// [`ClaudeExecution`] once the terminal `result` event has been
+    /// observed.
+    ///
+    /// The `claude` child is spawned as its own process group leader, so
+    /// [`StreamHandle::kill`] (and dropping the handle before it finishes)
+    /// terminates the whole tree it spawned, not just the CLI process
+    /// itself.
+    ///
+    /// The child's stdout is read line-by-line on a background thread; each
+    /// line is expected to be one complete JSON object. Lines with an
+    /// unrecognized `type` are skipped rather than aborting the stream. If
+    /// the process exits non-zero, any buffered events are emitted first and
+    /// the final `Result` carries `ExecutorError::ClaudeFailed` built from
+    /// stderr.
+    pub fn execute_streaming(
+        &self,
+        prompt: ClaudePrompt,
+    ) -> Result<(Receiver<StreamEvent>, StreamHandle), ExecutorError> {
+        let start = std::time::Instant::now();
+
+        let mut cmd = Command::new(&self.claude_binary);
+        cmd.current_dir(&self.working_directory);
+        cmd.arg("--output-format").arg("stream-json");
+
+        if let Some(ref model) = self.model {
+            cmd.arg("--model").arg(model);
+        }
+
+        if let Some(ref session_id) = prompt.resume_session_id {
+            cmd.arg("--resume").arg(session_id);
+        } else if prompt.continue_session {
+            cmd.arg("--continue");
+        }
+
+        if self.skip_permissions {
+            cmd.arg("--dangerously-skip-permissions");
+        } else {
+            let (allowed, disallowed) = self.resolve_tool_flags();
+            if allowed.is_some() || disallowed.is_some() {
+                if let Some(allowed) = allowed {
+                    cmd.arg("--allowedTools").arg(allowed);
+                }
+                if let Some(disallowed) = disallowed {
+                    cmd.arg("--disallowedTools").arg(disallowed);
+                }
+            } else {
+                cmd.arg("--allowedTools").arg(DEFAULT_ALLOWED_TOOLS);
+            }
+        }
+
+        if let Some(mcp_config) = self.materialize_mcp_config()? {
+            cmd.arg("--mcp-config").arg(mcp_config);
+        }
+
+        cmd.arg("-p");
+        cmd.arg(&prompt.text);
+
+        cmd.stdout(Stdio::piped());
+        cmd.stderr(Stdio::piped());
+
+        // Make the CLI its own process group leader so a later `kill()` (or
+        // the handle being dropped early) can signal the whole tree it
+        // spawns, not just the CLI process itself.
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::CommandExt;
+            cmd.process_group(0);
+        }
+
+        let mut child = cmd.spawn()
+            .map_err(|e| ExecutorError::ExecutionFailed(e.to_string()))?;
+
+        let stdout = child.stdout.take()
+            .ok_or_else(|| ExecutorError::ExecutionFailed("failed to capture stdout".to_string()))?;
+
+        let child = Arc::new(Mutex::new(child));
+        let child_for_thread = Arc::clone(&child);
+
+        let (tx, rx) = mpsc::channel();
+
+        let permission_profile = self.permission_profile.clone();
+        let permission_handler = self.permission_handler.clone();
+        let working_directory = self.working_directory.clone();
+
+        let join = std::thread::spawn(move || -> Result<ClaudeExecution, ExecutorError> {
+            let reader = BufReader::new(stdout);
+            let mut final_event: Option<StreamEvent> = None;
+            let observer = EnvironmentObserver::new(working_directory);
+
+            for line in reader.lines() {
+                let line = line.map_err(|e| ExecutorError::ExecutionFailed(e.to_string()))?;
+                if line.trim().is_empty() {
+                    continue;
+                }
+
+                let raw: RawStreamLine = match serde_json::from_str(&line) {
+                    Ok(raw) => raw,
+                    Err(_) => continue, // Malformed line; skip rather than abort the stream.
+                };
+
+                if let Some(event) = raw.into_event() {
+                    if let (StreamEvent::ToolUseStarted { name, input, .. }, Some(handler)) =
+                        (&event, &permission_handler)
+                    {
+                        let args = input.to_string();
+                        let covered = permission_profile.lock().unwrap().is_covered(name, &args);
+                        if !covered {
+                            if let Ok(snapshot) = observer.snapshot() {
+                                let request = ToolRequest { tool: name.clone(), args, snapshot };
+                                let decision = (handler.lock().unwrap())(&request);
+                                match decision {
+                                    PermissionDecision::AllowAndRemember => {
+                                        permission_profile.lock().unwrap().remember(request.tool, true);
+                                    }
+                                    PermissionDecision::DenyAndRemember => {
+                                        permission_profile.lock().unwrap().remember(request.tool, false);
+                                    }
+                                    PermissionDecision::Allow | PermissionDecision::Deny => {}
+                                }
+                            }
+                        }
+                    }
+
+                    if let StreamEvent::FinalResult { .. } = &event {
+                        final_event = Some(event.clone());
+                    }
+                    // Receiver may already be gone if the caller dropped it; that's fine.
+                    let _ = tx.send(event);
+                }
+            }
+
+            let status = {
+                let mut child = child_for_thread.lock().unwrap();
+                child.wait()
+                    .map_err(|e| ExecutorError::ExecutionFailed(e.to_string()))?
+            };
+
+            if !status.success() {
+                let mut stderr = String::new();
+                if let Some(mut err) = child_for_thread.lock().unwrap().stderr.take() {
+                    use std::io::Read;
+                    let _ = err.read_to_string(&mut stderr);
+                }
+                return Err(ExecutorError::ClaudeFailed(stderr));
+            }
+
+            match final_event {
+                Some(StreamEvent::FinalResult { session_id, cost_usd, result, model }) => {
+                    Ok(ClaudeExecution {
+                        prompt,
+                        response: result,
+                        session_id,
+                        cost: cost_usd,
+                        duration_ms: start.elapsed().as_millis() as u64,
+                        model: model.unwrap_or_else(|| "unknown".to_string()),
+                        timestamp: Utc::now(),
+                    })
+                }
+                _ => Err(ExecutorError::ParseError(
+                    "stream ended without a terminal result event".to_string(),
+                )),
+            }
+        });
+
+        Ok((rx, StreamHandle { child, join: Some(join) }))
+    }
+}
+
+/// Handle to an in-flight [`ClaudeExecutor::execute_streaming`] run: lets a
+/// caller kill the whole child process tree early (e.g. a user-initiated
+/// cancellation) and, separately, wait for the final [`ClaudeExecution`]
+/// once the stream ends, whether that's because the turn finished or
+/// because it was killed.
+///
+/// Dropping the handle without calling [`StreamHandle::join`] kills the
+/// process tree too, so a caller that abandons a stream (e.g. a UI that
+/// navigates away) doesn't leak a running `claude` process.
+pub struct StreamHandle {
+    child: Arc<Mutex<Child>>,
+    join: Option<std::thread::JoinHandle<Result<ClaudeExecution, ExecutorError>>>,
+}
+
+impl StreamHandle {
+    /// Terminate the whole process group immediately. Safe to call more than
+    /// once, and safe to call after the run has already finished on its own.
+    pub fn kill(&self) {
+        if let Ok(mut child) = self.child.lock() {
+            if matches!(child.try_wait(), Ok(None)) {
+                terminate_then_kill_group(&mut child);
+            }
+        }
+    }
+
+    /// Block until the stream ends, naturally or via [`StreamHandle::kill`],
+    /// and return the assembled execution.
+    pub fn join(mut self) -> Result<ClaudeExecution, ExecutorError> {
+        self.join.take().unwrap().join().unwrap_or_else(|_| {
+            Err(ExecutorError::ExecutionFailed("streaming thread panicked".to_string()))
+        })
+    }
+}
+
+impl Drop for StreamHandle {
+    fn drop(&mut self) {
+        self.kill();
+    }
 }
 
 // Expected JSON structure from claude --output-format json
@@ -182,4 +807,13 @@ pub enum ExecutorError {
     
     #[error("Failed to parse Claude response: {0}")]
     ParseError(String),
+
+    #[error("Tool server error: {0}")]
+    McpError(#[from] mcp::McpError),
+
+    #[error("Execution timed out after {0:?}")]
+    Timeout(Duration),
+
+    #[error("Execution was cancelled")]
+    Cancelled,
 }
\ No newline at end of file
@@ -0,0 +1,396 @@
+//! External tool-server registration (MCP-style) for [`ClaudeExecutor`].
+//!
+//! A [`ToolServerSpec`] describes a subprocess to launch alongside Claude;
+//! [`ToolServerHandle::spawn`] launches it, speaks the newline-delimited
+//! JSON-RPC handshake MCP servers use (`initialize` then `tools/list`) to
+//! discover what tools it exposes, and keeps the child alive until dropped.
+//! [`write_mcp_config`] materializes every registered spec into the JSON
+//! file format Claude's own `--mcp-config` flag expects, so Claude launches
+//! (and owns) its own copy of each server for the actual conversation while
+//! this handle's copy exists purely for discovery. [`PluginRegistry`] tracks
+//! multiple handles by name and routes direct `call`s to the one that
+//! advertised a given tool, for SDK-side code that wants to invoke a plugin
+//! without going through a Claude turn at all.
+//!
+//! [`ClaudeExecutor`]: super::executor::ClaudeExecutor
+
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+use serde::{Deserialize, Serialize};
+
+/// A registered external tool server: a command to launch, the arguments to
+/// pass it, and any environment variables it needs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolServerSpec {
+    pub name: String,
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+}
+
+impl ToolServerSpec {
+    pub fn new(
+        name: impl Into<String>,
+        command: impl Into<String>,
+        args: Vec<String>,
+        env: HashMap<String, String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            command: command.into(),
+            args,
+            env,
+        }
+    }
+}
+
+/// A running tool-server subprocess, plus the tool names it advertised
+/// during the handshake. The child is killed when this handle is dropped.
+pub struct ToolServerHandle {
+    spec: ToolServerSpec,
+    child: Child,
+    stdin: ChildStdin,
+    reader: BufReader<ChildStdout>,
+    tools: Vec<String>,
+}
+
+impl ToolServerHandle {
+    /// Spawn `spec`'s command with piped stdio and perform the
+    /// `initialize`/`tools/list` JSON-RPC handshake to discover its tools.
+    pub fn spawn(spec: ToolServerSpec) -> Result<Self, McpError> {
+        let mut cmd = Command::new(&spec.command);
+        cmd.args(&spec.args);
+        for (key, value) in &spec.env {
+            cmd.env(key, value);
+        }
+        cmd.stdin(Stdio::piped());
+        cmd.stdout(Stdio::piped());
+        cmd.stderr(Stdio::piped());
+
+        let mut child = cmd
+            .spawn()
+            .map_err(|e| McpError::SpawnFailed(spec.name.clone(), e.to_string()))?;
+
+        let stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| McpError::HandshakeFailed(spec.name.clone(), "no stdin".to_string()))?;
+        let stdout = child.stdout.take().ok_or_else(|| {
+            McpError::HandshakeFailed(spec.name.clone(), "no stdout".to_string())
+        })?;
+
+        let mut handle = Self {
+            spec,
+            child,
+            stdin,
+            reader: BufReader::new(stdout),
+            tools: Vec::new(),
+        };
+
+        handle.handshake()?;
+        Ok(handle)
+    }
+
+    fn handshake(&mut self) -> Result<(), McpError> {
+        self.send(
+            1,
+            "initialize",
+            serde_json::json!({
+                "protocolVersion": "2024-11-05",
+                "capabilities": {},
+                "clientInfo": { "name": "claude_sdk", "version": env!("CARGO_PKG_VERSION") },
+            }),
+        )?;
+        self.recv()?;
+
+        self.send(2, "tools/list", serde_json::json!({}))?;
+        let response = self.recv()?;
+
+        self.tools = response
+            .get("result")
+            .and_then(|r| r.get("tools"))
+            .and_then(|t| t.as_array())
+            .map(|tools| {
+                tools
+                    .iter()
+                    .filter_map(|t| t.get("name").and_then(|n| n.as_str()))
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok(())
+    }
+
+    fn send(&mut self, id: u64, method: &str, params: serde_json::Value) -> Result<(), McpError> {
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": method,
+            "params": params,
+        });
+        writeln!(self.stdin, "{}", request).map_err(McpError::Io)?;
+        self.stdin.flush().map_err(McpError::Io)
+    }
+
+    fn recv(&mut self) -> Result<serde_json::Value, McpError> {
+        let mut line = String::new();
+        loop {
+            line.clear();
+            let bytes = self.reader.read_line(&mut line).map_err(McpError::Io)?;
+            if bytes == 0 {
+                return Err(McpError::HandshakeFailed(
+                    self.spec.name.clone(),
+                    "server closed stdout before responding".to_string(),
+                ));
+            }
+            if line.trim().is_empty() {
+                continue;
+            }
+            return serde_json::from_str(&line).map_err(|e| {
+                McpError::HandshakeFailed(self.spec.name.clone(), e.to_string())
+            });
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.spec.name
+    }
+
+    /// The spec this handle was spawned from.
+    pub fn spec(&self) -> &ToolServerSpec {
+        &self.spec
+    }
+
+    /// Tool names this server advertised via `tools/list`.
+    pub fn tools(&self) -> &[String] {
+        &self.tools
+    }
+
+    /// Issue a `tools/call` JSON-RPC request for `tool_name` with `input` and
+    /// return the raw `result` value from the response. Callers turn this
+    /// into a `ToolResultContent` the same way they would any other tool
+    /// result.
+    pub fn call(&mut self, tool_name: &str, input: serde_json::Value) -> Result<serde_json::Value, McpError> {
+        self.send(
+            3,
+            "tools/call",
+            serde_json::json!({ "name": tool_name, "arguments": input }),
+        )?;
+        let response = self.recv()?;
+        response.get("result").cloned().ok_or_else(|| {
+            McpError::CallFailed(self.spec.name.clone(), tool_name.to_string(), "no result in response".to_string())
+        })
+    }
+}
+
+impl Drop for ToolServerHandle {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// Write `specs` to `path` in the JSON shape Claude's `--mcp-config` flag
+/// expects: `{"mcpServers": {name: {command, args, env}}}`.
+pub fn write_mcp_config(path: &Path, specs: &[ToolServerSpec]) -> Result<(), McpError> {
+    let servers: HashMap<&str, serde_json::Value> = specs
+        .iter()
+        .map(|spec| {
+            (
+                spec.name.as_str(),
+                serde_json::json!({
+                    "command": spec.command,
+                    "args": spec.args,
+                    "env": spec.env,
+                }),
+            )
+        })
+        .collect();
+
+    let config = serde_json::json!({ "mcpServers": servers });
+    let body = serde_json::to_string_pretty(&config).map_err(|e| McpError::Io(std::io::Error::new(std::io::ErrorKind::Other, e)))?;
+    std::fs::write(path, body).map_err(McpError::Io)
+}
+
+/// Tracks spawned [`ToolServerHandle`]s by name and routes `call`s to
+/// whichever one advertised the requested tool, so a caller that doesn't
+/// hand the whole MCP config off to the `claude` subprocess (e.g. a plugin
+/// invoked directly by SDK-side code, outside of a Claude turn) can still
+/// reach it. Every registered handle is killed when the registry is
+/// dropped, since `ToolServerHandle` kills its child on drop.
+#[derive(Default)]
+pub struct PluginRegistry {
+    handles: HashMap<String, ToolServerHandle>,
+}
+
+impl PluginRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Spawn `spec`, perform its capability handshake, and register it under
+    /// `spec.name`.
+    pub fn register(&mut self, spec: ToolServerSpec) -> Result<(), McpError> {
+        let name = spec.name.clone();
+        let handle = ToolServerHandle::spawn(spec)?;
+        self.handles.insert(name, handle);
+        Ok(())
+    }
+
+    /// Remove and shut down a previously registered plugin.
+    pub fn unregister(&mut self, name: &str) {
+        self.handles.remove(name);
+    }
+
+    /// Every tool name advertised across all registered plugins.
+    pub fn tools(&self) -> Vec<String> {
+        let mut tools: Vec<String> = self
+            .handles
+            .values()
+            .flat_map(|h| h.tools().iter().cloned())
+            .collect();
+        tools.sort();
+        tools.dedup();
+        tools
+    }
+
+    /// Call `tool_name` with `input` on whichever registered plugin
+    /// advertised it.
+    pub fn call(&mut self, tool_name: &str, input: serde_json::Value) -> Result<serde_json::Value, McpError> {
+        let handle = self
+            .handles
+            .values_mut()
+            .find(|h| h.tools().iter().any(|t| t == tool_name))
+            .ok_or_else(|| McpError::UnknownTool(tool_name.to_string()))?;
+        handle.call(tool_name, input)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum McpError {
+    #[error("failed to spawn tool server `{0}`: {1}")]
+    SpawnFailed(String, String),
+
+    #[error("JSON-RPC handshake with tool server `{0}` failed: {1}")]
+    HandshakeFailed(String, String),
+
+    #[error("call to `{1}` on tool server `{0}` failed: {2}")]
+    CallFailed(String, String, String),
+
+    #[error("no registered plugin advertises tool `{0}`")]
+    UnknownTool(String),
+
+    #[error("IO error talking to tool server: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    /// Build a spec for a `sh`-scripted fake MCP server: it replies to the
+    /// `initialize`/`tools/list` handshake with `tools`, then answers every
+    /// subsequent `tools/call` with `call_result` verbatim, so the real
+    /// JSON-RPC framing (newline-delimited requests/responses over piped
+    /// stdio) is exercised without a real plugin subprocess.
+    fn fake_server_spec(tools: &[&str], call_result: &str) -> ToolServerSpec {
+        let tools_json = tools
+            .iter()
+            .map(|t| format!(r#"{{"name":"{t}"}}"#))
+            .collect::<Vec<_>>()
+            .join(",");
+        let script = format!(
+            "read _init\n\
+             printf '{{\"jsonrpc\":\"2.0\",\"id\":1,\"result\":{{}}}}\\n'\n\
+             read _list\n\
+             printf '{{\"jsonrpc\":\"2.0\",\"id\":2,\"result\":{{\"tools\":[{tools_json}]}}}}\\n'\n\
+             while read -r _call; do\n\
+             printf '{{\"jsonrpc\":\"2.0\",\"id\":3,\"result\":{call_result}}}\\n'\n\
+             done\n"
+        );
+        ToolServerSpec::new(
+            format!("fake-{}", Uuid::new_v4()),
+            "sh",
+            vec!["-c".to_string(), script],
+            HashMap::new(),
+        )
+    }
+
+    #[test]
+    fn spawn_performs_handshake_and_records_advertised_tools() {
+        let handle = ToolServerHandle::spawn(fake_server_spec(&["echo", "add"], "null")).unwrap();
+        let mut tools = handle.tools().to_vec();
+        tools.sort();
+        assert_eq!(tools, vec!["add".to_string(), "echo".to_string()]);
+    }
+
+    #[test]
+    fn call_returns_the_result_field_of_the_response() {
+        let mut handle =
+            ToolServerHandle::spawn(fake_server_spec(&["echo"], r#"{"output":"hi"}"#)).unwrap();
+        let result = handle.call("echo", serde_json::json!({"text": "hi"})).unwrap();
+        assert_eq!(result, serde_json::json!({"output": "hi"}));
+    }
+
+    #[test]
+    fn registry_routes_calls_to_the_handle_that_advertised_the_tool() {
+        let mut registry = PluginRegistry::new();
+        registry
+            .register(fake_server_spec(&["only-here"], r#"{"ok":true}"#))
+            .unwrap();
+
+        assert_eq!(registry.tools(), vec!["only-here".to_string()]);
+        let result = registry.call("only-here", serde_json::json!({})).unwrap();
+        assert_eq!(result, serde_json::json!({"ok": true}));
+
+        assert!(matches!(
+            registry.call("missing", serde_json::json!({})),
+            Err(McpError::UnknownTool(name)) if name == "missing"
+        ));
+    }
+
+    #[test]
+    fn unregister_removes_the_plugin_and_its_tools() {
+        let mut registry = PluginRegistry::new();
+        let spec = fake_server_spec(&["gone-soon"], "null");
+        let name = spec.name.clone();
+        registry.register(spec).unwrap();
+        assert_eq!(registry.tools(), vec!["gone-soon".to_string()]);
+
+        registry.unregister(&name);
+        assert!(registry.tools().is_empty());
+    }
+
+    #[test]
+    fn write_mcp_config_matches_the_mcpservers_shape_claude_expects() {
+        let path = std::env::temp_dir().join(format!("claude_sdk_mcp_config_test_{}.json", Uuid::new_v4()));
+        let mut env = HashMap::new();
+        env.insert("FOO".to_string(), "bar".to_string());
+        let specs = vec![ToolServerSpec::new("demo", "demo-server", vec!["--flag".to_string()], env)];
+
+        write_mcp_config(&path, &specs).unwrap();
+        let written: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+
+        assert_eq!(
+            written,
+            serde_json::json!({
+                "mcpServers": {
+                    "demo": {
+                        "command": "demo-server",
+                        "args": ["--flag"],
+                        "env": {"FOO": "bar"},
+                    }
+                }
+            })
+        );
+
+        std::fs::remove_file(&path).ok();
+    }
+}
@@ -1,12 +1,36 @@
+pub mod blob_store;
+pub mod compression;
+pub mod evaluation;
 pub mod executor;
+pub mod mcp;
 pub mod observer;
+pub mod permissions;
 pub mod recorder;  // Keep temporarily for Transition type
+pub mod replay;
+pub mod store;
+pub mod tool_graph;
 pub mod workspace;
 pub mod conversation;
+pub mod environment;
 
 // Core types
-pub use executor::{ClaudeExecutor, ClaudePrompt, ClaudeExecution, ExecutorError};
-pub use observer::{EnvironmentObserver, EnvironmentSnapshot, ObserverError};
-pub use recorder::Transition;  // Move this type out of recorder later
+pub use blob_store::CasStore;
+pub use compression::{CharsPerTokenEstimator, CompressionReport, TokenEstimator};
+pub use evaluation::{Case, CaseResult, Report, Runner, Suite};
+pub use executor::{
+    CancellationToken, ClaudeExecutor, ClaudePrompt, ClaudeExecution, ExecutorError, StreamEvent,
+    StreamHandle,
+};
+pub use mcp::{McpError, PluginRegistry, ToolServerHandle, ToolServerSpec};
+pub use permissions::{Capability, Decision, Permission, PermissionDecision, PermissionError, Scope, ToolRequest};
+pub use observer::{
+    CacheStats, DefaultSessionLocator, DirSessionLocator, EnvironmentObserver, EnvironmentSnapshot,
+    FileSnapshot, ObserverError, SessionLocator, SnapshotConfig, WatchHandle, WorkspaceChangeEvent,
+};
+pub use recorder::{ChangeKind, DiffHunk, FileChange, SnapshotDiff, Transition, TransitionMetadata};  // Move this type out of recorder later
+pub use replay::{Replay, ReplayState};
+pub use store::{ConversationEntry, ConversationStore};
+pub use tool_graph::{PendingToolCall, ToolCallGraph, ToolCallStatus};
 pub use workspace::{Workspace, WorkspaceError};
-pub use conversation::{Conversation, ConversationError, ConversationMetadata};
\ No newline at end of file
+pub use conversation::{Conversation, ConversationError, ConversationMetadata, RecordingPolicy};
+pub use environment::{ClaudeEnvironment, EnvironmentError};
\ No newline at end of file
@@ -1,22 +1,105 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::collections::HashMap;
-use std::sync::Arc;
-use glob::glob;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use ignore::WalkBuilder;
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
 
 /// Sentinel session ID used before any conversation has started.
 pub const PRE_CONVERSATION_SESSION_ID: &str = "\u{2205}"; // "∅"
 /// Sentinel path for the non-existent session file before first turn.
 pub const NO_SESSION_FILE: &str = "<none>";
 use crate::parser::SessionParser;
-use crate::types::ParsedSession;
+use crate::types::{ParsedSession, ToolExecution};
+use super::recorder::FileChange;
 use crate::utils::path::encode_project_path;
 
+/// How long [`EnvironmentObserver::watch`] waits for filesystem activity to
+/// go quiet before it snapshots and fires the callback, coalescing a burst
+/// of rapid writes (e.g. a Claude tool call touching many files) into one
+/// snapshot instead of one per event.
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// One tick of [`EnvironmentObserver::watch_changes`]: a fresh snapshot plus
+/// what changed since the previously emitted one. Empty on the first tick,
+/// since there's nothing yet to diff against. `tool_executions` is always
+/// empty: the snapshots `watch` emits carry no parsed session.
+#[derive(Debug, Clone)]
+pub struct WorkspaceChangeEvent {
+    pub snapshot: EnvironmentSnapshot,
+    pub file_changes: Vec<FileChange>,
+    pub tool_executions: Vec<ToolExecution>,
+}
+
+/// A single file's state within an [`EnvironmentSnapshot`].
+///
+/// `hash` is always populated (a stable content hash, so two snapshots can be
+/// compared cheaply without holding onto every file body) and `size` always
+/// reflects the file's real byte length, even when `body` is absent. `body`
+/// carries the actual UTF-8 content unless the file was detected as binary
+/// (a NUL byte in its first 8 KiB), isn't valid UTF-8, exceeded
+/// `SnapshotConfig::max_file_bytes`, or `SnapshotConfig::hash_only` was set —
+/// in which case only the hash/size placeholder survives.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileSnapshot {
+    pub hash: String,
+    pub size: u64,
+    pub body: Option<String>,
+}
+
+/// Controls how [`EnvironmentObserver`] crawls the workspace when building a
+/// snapshot.
+#[derive(Debug, Clone)]
+pub struct SnapshotConfig {
+    /// Files larger than this are hashed but their body is not retained.
+    pub max_file_bytes: u64,
+    /// Extra ignore patterns layered on top of `.gitignore`/`.ignore`/global excludes.
+    pub extra_ignores: Vec<String>,
+    /// Whether the walk should follow symlinks.
+    pub follow_symlinks: bool,
+    /// If true, never retain file bodies, only hashes.
+    pub hash_only: bool,
+    /// Optional max directory depth to descend while walking.
+    pub max_depth: Option<usize>,
+    /// If set, only files whose extension (without the leading dot) is in
+    /// this set are walked at all; everything else is skipped before it's
+    /// even hashed. `None` means no allowlist filtering.
+    pub allowed_extensions: Option<std::collections::HashSet<String>>,
+    /// Extensions (without the leading dot) to always skip, checked after
+    /// `allowed_extensions`. Lets callers exclude a few noisy extensions
+    /// without having to enumerate everything else as an allowlist.
+    pub denied_extensions: std::collections::HashSet<String>,
+    /// Whether to respect `.gitignore`/`.ignore`/global git excludes while
+    /// walking. Disabling this still applies `extra_ignores` and the
+    /// extension allow/deny sets.
+    pub respect_gitignore: bool,
+}
+
+impl Default for SnapshotConfig {
+    fn default() -> Self {
+        Self {
+            max_file_bytes: 256 * 1024,
+            extra_ignores: Vec::new(),
+            follow_symlinks: false,
+            hash_only: false,
+            max_depth: None,
+            allowed_extensions: None,
+            denied_extensions: std::collections::HashSet::new(),
+            respect_gitignore: true,
+        }
+    }
+}
+
 // Keep the path-based snapshot for serialization
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EnvironmentSnapshot {
-    pub files: HashMap<PathBuf, String>,
+    pub files: HashMap<PathBuf, FileSnapshot>,
     pub session_file: PathBuf,  // Store path for serialization
     pub session_id: Option<String>,  // Session ID for reconstruction
     pub timestamp: DateTime<Utc>,
@@ -24,33 +107,224 @@ pub struct EnvironmentSnapshot {
     pub session: Option<Arc<ParsedSession>>,  // Shared via Arc for cloning
 }
 
+/// Finds the Claude session file(s) backing a workspace.
+///
+/// The default implementation ([`DefaultSessionLocator`]) reproduces the
+/// historical behavior of reading from `~/.claude/projects/{encoded}/`,
+/// which makes `snapshot`/`snapshot_with_session` impossible to exercise
+/// without a real Claude run and unusable once the host relocates or
+/// sandboxes that directory. Swap in a different locator (e.g.
+/// [`DirSessionLocator`]) via [`EnvironmentObserver::with_locator`] or the
+/// [`EnvironmentObserver::with_session_dir`] shorthand to point discovery
+/// somewhere else, such as a fixture directory in tests.
+pub trait SessionLocator: Send + Sync {
+    /// Most recently modified session file for `workspace`.
+    fn active_session_file(&self, workspace: &Path) -> Result<PathBuf, ObserverError>;
+
+    /// The session file for a specific, already-known session ID.
+    fn session_file_by_id(
+        &self,
+        workspace: &Path,
+        session_id: &str,
+    ) -> Result<PathBuf, ObserverError>;
+}
+
+/// Reproduces Claude Code's own layout: session files live under
+/// `~/.claude/projects/{encoded_workspace_path}/{session_id}.jsonl`.
+pub struct DefaultSessionLocator;
+
+impl SessionLocator for DefaultSessionLocator {
+    fn active_session_file(&self, workspace: &Path) -> Result<PathBuf, ObserverError> {
+        let project_dir = claude_project_dir(workspace)?;
+
+        let mut session_files: Vec<_> = std::fs::read_dir(&project_dir)
+            .map_err(|e| ObserverError::IoError(e.to_string()))?
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| {
+                entry.path().extension()
+                    .map(|ext| ext == "jsonl")
+                    .unwrap_or(false)
+            })
+            .collect();
+
+        session_files.sort_by_key(|entry| {
+            entry.metadata()
+                .and_then(|m| m.modified())
+                .unwrap_or(std::time::SystemTime::UNIX_EPOCH)
+        });
+
+        session_files
+            .last()
+            .map(|entry| entry.path())
+            .ok_or_else(|| ObserverError::NoSessionFound)
+    }
+
+    fn session_file_by_id(
+        &self,
+        workspace: &Path,
+        session_id: &str,
+    ) -> Result<PathBuf, ObserverError> {
+        let project_dir = claude_project_dir(workspace)?;
+        let session_file = project_dir.join(format!("{}.jsonl", session_id));
+
+        if !session_file.exists() {
+            return Err(ObserverError::NoSessionFound);
+        }
+
+        Ok(session_file)
+    }
+}
+
+/// Resolve `~/.claude/projects/{encoded_workspace}`, the directory
+/// [`DefaultSessionLocator`] reads from.
+fn claude_project_dir(workspace: &Path) -> Result<PathBuf, ObserverError> {
+    let claude_projects = home::home_dir()
+        .ok_or(ObserverError::HomeNotFound)?
+        .join(".claude")
+        .join("projects");
+
+    let project_name = encode_project_path(workspace);
+    let project_dir = claude_projects.join(&project_name);
+
+    if !project_dir.exists() {
+        return Err(ObserverError::ProjectNotFound(format!(
+            "Project directory not found: {:?} (encoded from workspace: {:?})",
+            project_dir, workspace
+        )));
+    }
+
+    Ok(project_dir)
+}
+
+/// Looks for `{session_id}.jsonl` files directly under a fixed directory,
+/// bypassing `~/.claude/projects` entirely. Intended for tests and for
+/// sandboxed or relocated hosts that keep session files somewhere else.
+pub struct DirSessionLocator {
+    dir: PathBuf,
+}
+
+impl DirSessionLocator {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+}
+
+impl SessionLocator for DirSessionLocator {
+    fn active_session_file(&self, _workspace: &Path) -> Result<PathBuf, ObserverError> {
+        let mut session_files: Vec<_> = std::fs::read_dir(&self.dir)
+            .map_err(|e| ObserverError::IoError(e.to_string()))?
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| {
+                entry.path().extension()
+                    .map(|ext| ext == "jsonl")
+                    .unwrap_or(false)
+            })
+            .collect();
+
+        session_files.sort_by_key(|entry| {
+            entry.metadata()
+                .and_then(|m| m.modified())
+                .unwrap_or(std::time::SystemTime::UNIX_EPOCH)
+        });
+
+        session_files
+            .last()
+            .map(|entry| entry.path())
+            .ok_or_else(|| ObserverError::NoSessionFound)
+    }
+
+    fn session_file_by_id(
+        &self,
+        _workspace: &Path,
+        session_id: &str,
+    ) -> Result<PathBuf, ObserverError> {
+        let session_file = self.dir.join(format!("{}.jsonl", session_id));
+        if !session_file.exists() {
+            return Err(ObserverError::NoSessionFound);
+        }
+        Ok(session_file)
+    }
+}
+
 pub struct EnvironmentObserver {
     workspace: PathBuf,
-    file_patterns: Vec<String>,
+    snapshot_config: SnapshotConfig,
+    locator: Arc<dyn SessionLocator>,
+    hash_cache: Mutex<HashMap<PathBuf, CachedHash>>,
+    cache_stats: Mutex<CacheStats>,
+}
+
+/// A previously computed hash for one file, keyed by the mtime/size it was
+/// observed at. [`EnvironmentObserver::snapshot_files`] reuses the hash
+/// as-is when a later walk sees the same mtime and size, and only rehashes
+/// when either changed.
+#[derive(Debug, Clone)]
+struct CachedHash {
+    modified: SystemTime,
+    size: u64,
+    hash: String,
+}
+
+/// Hit/miss counters for [`EnvironmentObserver`]'s mtime+size hash cache,
+/// returned by [`EnvironmentObserver::cache_stats`]. A hit means a file's
+/// hash was reused from a previous `snapshot_files` call without rehashing
+/// its bytes; a miss means it was hashed (first time seen, or its mtime/size
+/// changed).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
 }
 
 impl EnvironmentObserver {
     pub fn new(workspace: PathBuf) -> Self {
+        Self::with_config(workspace, SnapshotConfig::default())
+    }
+
+    /// Create an observer with a custom [`SnapshotConfig`] governing how
+    /// `snapshot_files` walks the workspace.
+    pub fn with_config(workspace: PathBuf, snapshot_config: SnapshotConfig) -> Self {
         Self {
-            workspace: workspace.clone(),
-            file_patterns: vec![
-                "**/*.py".to_string(),
-                "**/*.rs".to_string(),
-                "**/*.js".to_string(),
-                "**/*.ts".to_string(),
-                "**/*.jsx".to_string(),
-                "**/*.tsx".to_string(),
-                "**/*.json".to_string(),
-                "**/*.toml".to_string(),
-                "**/*.yaml".to_string(),
-                "**/*.yml".to_string(),
-                "**/*.md".to_string(),
-                "**/Dockerfile".to_string(),
-                "**/.gitignore".to_string(),
-            ],
+            workspace,
+            snapshot_config,
+            locator: Arc::new(DefaultSessionLocator),
+            hash_cache: Mutex::new(HashMap::new()),
+            cache_stats: Mutex::new(CacheStats::default()),
         }
     }
-    
+
+    /// Create an observer that discovers session files via `locator`
+    /// instead of the default `~/.claude/projects` layout.
+    pub fn with_locator(workspace: PathBuf, locator: Arc<dyn SessionLocator>) -> Self {
+        Self {
+            workspace,
+            snapshot_config: SnapshotConfig::default(),
+            locator,
+            hash_cache: Mutex::new(HashMap::new()),
+            cache_stats: Mutex::new(CacheStats::default()),
+        }
+    }
+
+    /// Hit/miss counts for the mtime+size hash cache since this observer was
+    /// created (or since `with_config` was cloned into a fresh one, e.g. by
+    /// [`EnvironmentObserver::watch_with`] — each observer tracks its own).
+    pub fn cache_stats(&self) -> CacheStats {
+        *self.cache_stats.lock().unwrap()
+    }
+
+    /// Swap in a [`DirSessionLocator`] pointed at `dir`, so session
+    /// discovery reads `{dir}/{session_id}.jsonl` instead of
+    /// `~/.claude/projects`.
+    pub fn with_session_dir(mut self, dir: PathBuf) -> Self {
+        self.locator = Arc::new(DirSessionLocator::new(dir));
+        self
+    }
+
+    /// Replace the session locator in place.
+    pub fn set_locator(&mut self, locator: Arc<dyn SessionLocator>) {
+        self.locator = locator;
+    }
+
     pub fn snapshot(&self) -> Result<EnvironmentSnapshot, ObserverError> {
         let files = self.snapshot_files()?;
         let session_file = self.find_active_session_file()?;
@@ -94,95 +368,277 @@ impl EnvironmentObserver {
         })
     }
     
-    pub fn snapshot_files(&self) -> Result<HashMap<PathBuf, String>, ObserverError> {
+    /// Walk the workspace respecting `.gitignore`, `.ignore`, and global git
+    /// excludes (via the `ignore` crate's `WalkBuilder`, unless
+    /// `SnapshotConfig::respect_gitignore` is disabled), hashing every file
+    /// it finds and keeping the body when it's UTF-8 text under
+    /// `max_file_bytes`.
+    pub fn snapshot_files(&self) -> Result<HashMap<PathBuf, FileSnapshot>, ObserverError> {
         let mut files = HashMap::new();
-        
-        for pattern in &self.file_patterns {
-            let full_pattern = self.workspace.join(pattern);
-            let pattern_str = full_pattern.to_string_lossy();
-            
-            for entry in glob(&pattern_str).map_err(|e| ObserverError::GlobError(e.to_string()))? {
-                match entry {
-                    Ok(path) => {
-                        // Skip directories and non-readable files
-                        if path.is_file() {
-                            if let Ok(content) = std::fs::read_to_string(&path) {
-                                // Store relative path
-                                if let Ok(relative) = path.strip_prefix(&self.workspace) {
-                                    files.insert(relative.to_path_buf(), content);
-                                }
-                            }
+        let config = &self.snapshot_config;
+
+        let mut builder = WalkBuilder::new(&self.workspace);
+        builder
+            .standard_filters(config.respect_gitignore)
+            .follow_links(config.follow_symlinks);
+        if let Some(max_depth) = config.max_depth {
+            builder.max_depth(Some(max_depth));
+        }
+        if !config.extra_ignores.is_empty() {
+            let mut overrides = ignore::overrides::OverrideBuilder::new(&self.workspace);
+            for pattern in &config.extra_ignores {
+                overrides
+                    .add(&format!("!{}", pattern))
+                    .map_err(|e| ObserverError::GlobError(e.to_string()))?;
+            }
+            builder.overrides(
+                overrides
+                    .build()
+                    .map_err(|e| ObserverError::GlobError(e.to_string()))?,
+            );
+        }
+
+        for entry in builder.build() {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(_) => continue,
+            };
+
+            let path = entry.path();
+            if !entry.file_type().map(|ft| ft.is_file()).unwrap_or(false) {
+                continue;
+            }
+
+            if !extension_allowed(path, config) {
+                continue;
+            }
+
+            let relative = match path.strip_prefix(&self.workspace) {
+                Ok(relative) => relative.to_path_buf(),
+                Err(_) => continue,
+            };
+
+            let bytes = match std::fs::read(path) {
+                Ok(bytes) => bytes,
+                Err(_) => continue,
+            };
+
+            let size = bytes.len() as u64;
+            let modified = entry.metadata().ok().and_then(|m| m.modified().ok());
+            let hash = self.hash_with_cache(&relative, modified, size, &bytes);
+            let body = if config.hash_only || size > config.max_file_bytes || looks_binary(&bytes) {
+                None
+            } else {
+                String::from_utf8(bytes).ok()
+            };
+
+            files.insert(relative, FileSnapshot { hash, size, body });
+        }
+
+        Ok(files)
+    }
+
+    
+    /// Watch the workspace for filesystem changes, invoking `callback` with
+    /// a fresh [`EnvironmentSnapshot`] each time activity settles.
+    ///
+    /// Uses a filesystem-notify backend under the hood. An event triggers a
+    /// snapshot only if it touches a non-hidden path that neither the
+    /// workspace's top-level `.gitignore` nor `SnapshotConfig::extra_ignores`
+    /// excludes; nested `.gitignore`/`.ignore` files, global git excludes and
+    /// the extension allow/deny sets are not applied to events, so a change
+    /// to such a path can still trigger a snapshot (whose contents do follow
+    /// every [`EnvironmentObserver::snapshot_files`] rule). Rapid bursts of events (e.g.
+    /// a Claude tool call rewriting many files) are coalesced: the watcher
+    /// waits for `WATCH_DEBOUNCE` (~200ms) of quiet before firing, so a
+    /// flurry of writes yields one snapshot rather than dozens.
+    ///
+    /// Returns a [`WatchHandle`]; dropping it (or calling `stop()`) stops
+    /// the watcher.
+    pub fn watch<F>(&self, callback: F) -> Result<WatchHandle, ObserverError>
+    where
+        F: FnMut(EnvironmentSnapshot) + Send + 'static,
+    {
+        self.watch_with(callback)
+    }
+
+    /// Like [`EnvironmentObserver::watch`], but delivers snapshots over a
+    /// channel instead of a callback, for callers that want to `recv()` them
+    /// on their own schedule.
+    pub fn watch_channel(
+        &self,
+    ) -> Result<(WatchHandle, Receiver<EnvironmentSnapshot>), ObserverError> {
+        let (tx, rx) = mpsc::channel();
+        let handle = self.watch_with(move |snapshot| {
+            let _ = tx.send(snapshot);
+        })?;
+        Ok((handle, rx))
+    }
+
+    fn watch_with<F>(&self, mut callback: F) -> Result<WatchHandle, ObserverError>
+    where
+        F: FnMut(EnvironmentSnapshot) + Send + 'static,
+    {
+        let (raw_tx, raw_rx) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+            if let Ok(event) = res {
+                let _ = raw_tx.send(event);
+            }
+        })
+        .map_err(|e| ObserverError::WatchError(e.to_string()))?;
+
+        watcher
+            .watch(&self.workspace, RecursiveMode::Recursive)
+            .map_err(|e| ObserverError::WatchError(e.to_string()))?;
+
+        let workspace = self.workspace.clone();
+        let matcher = build_ignore_matcher(&workspace, &self.snapshot_config);
+        let observer = EnvironmentObserver::with_config(workspace.clone(), self.snapshot_config.clone());
+        let stop = Arc::new(AtomicBool::new(false));
+        let stop_thread = stop.clone();
+
+        let thread = std::thread::spawn(move || loop {
+            if stop_thread.load(Ordering::Relaxed) {
+                return;
+            }
+
+            // Wait for the first relevant event.
+            loop {
+                match raw_rx.recv_timeout(Duration::from_millis(250)) {
+                    Ok(event) => {
+                        if event_is_relevant(&matcher, &workspace, &event) {
+                            break;
                         }
                     }
-                    Err(_) => continue,
+                    Err(RecvTimeoutError::Timeout) => {
+                        if stop_thread.load(Ordering::Relaxed) {
+                            return;
+                        }
+                    }
+                    Err(RecvTimeoutError::Disconnected) => return,
+                }
+            }
+
+            // Drain further events, resetting the quiet window each time one
+            // arrives, until `WATCH_DEBOUNCE` passes with nothing new.
+            loop {
+                match raw_rx.recv_timeout(WATCH_DEBOUNCE) {
+                    Ok(_) => continue,
+                    Err(RecvTimeoutError::Timeout) => break,
+                    Err(RecvTimeoutError::Disconnected) => return,
                 }
             }
+
+            if let Ok(files) = observer.snapshot_files() {
+                callback(EnvironmentSnapshot {
+                    files,
+                    session_file: PathBuf::from(NO_SESSION_FILE),
+                    session_id: None,
+                    timestamp: Utc::now(),
+                    session: None,
+                });
+            }
+        });
+
+        Ok(WatchHandle {
+            stop,
+            thread: Some(thread),
+            _watcher: watcher,
+        })
+    }
+
+    /// Snapshot the workspace, run `during` (typically a single
+    /// `ClaudeExecutor::execute` call), wait a fixed `WATCH_DEBOUNCE` (no
+    /// filesystem events are watched), snapshot again, and return `during`'s
+    /// output alongside the resulting [`FileChange`] list.
+    ///
+    /// This brackets an arbitrary unit of work with a before/after diff the
+    /// same way [`super::recorder::Transition::file_changes`] does for a
+    /// whole turn, but is useful on its own for callers (e.g. a tool
+    /// invoked directly, outside of `Conversation::send`) that want to know
+    /// exactly which files changed during one specific call.
+    pub fn watch_during<F, T>(&self, during: F) -> Result<(T, Vec<super::recorder::FileChange>), ObserverError>
+    where
+        F: FnOnce() -> T,
+    {
+        let before = EnvironmentSnapshot {
+            files: self.snapshot_files()?,
+            session_file: PathBuf::from(NO_SESSION_FILE),
+            session_id: None,
+            timestamp: Utc::now(),
+            session: None,
+        };
+
+        let result = during();
+
+        // Let rapid successive writes from the call settle before reading
+        // back the final state, mirroring `watch`'s debounce window.
+        std::thread::sleep(WATCH_DEBOUNCE);
+        let after = EnvironmentSnapshot {
+            files: self.snapshot_files()?,
+            session_file: PathBuf::from(NO_SESSION_FILE),
+            session_id: None,
+            timestamp: Utc::now(),
+            session: None,
+        };
+
+        Ok((result, super::recorder::diff_snapshots(&before, &after)))
+    }
+
+    /// Like [`EnvironmentObserver::watch`], but instead of a bare snapshot
+    /// emits a [`WorkspaceChangeEvent`] diffing each new snapshot against
+    /// the previously emitted one: which files changed since the last tick.
+    /// The session transcript is not watched or parsed (the snapshots
+    /// `watch` emits have no `session`), so the event's `tool_executions` is
+    /// always empty.
+    pub fn watch_changes<F>(&self, mut callback: F) -> Result<WatchHandle, ObserverError>
+    where
+        F: FnMut(WorkspaceChangeEvent) + Send + 'static,
+    {
+        let mut last: Option<EnvironmentSnapshot> = None;
+        self.watch(move |snapshot| {
+            let (file_changes, tool_executions) = match &last {
+                Some(previous) => (
+                    super::recorder::diff_snapshots(previous, &snapshot),
+                    super::recorder::tool_executions_between(previous, &snapshot),
+                ),
+                None => (Vec::new(), Vec::new()),
+            };
+            last = Some(snapshot.clone());
+            callback(WorkspaceChangeEvent { snapshot, file_changes, tool_executions });
+        })
+    }
+
+    /// Hash `bytes`, reusing a cached hash instead when `path` was last seen
+    /// at the same `modified`/`size` — so unchanged files are never rehashed
+    /// across consecutive `snapshot_files` calls, only read. Updates
+    /// [`EnvironmentObserver::cache_stats`] either way.
+    fn hash_with_cache(&self, path: &Path, modified: Option<SystemTime>, size: u64, bytes: &[u8]) -> String {
+        let Some(modified) = modified else {
+            self.cache_stats.lock().unwrap().misses += 1;
+            return hash_bytes(bytes);
+        };
+
+        let mut cache = self.hash_cache.lock().unwrap();
+        if let Some(cached) = cache.get(path) {
+            if cached.modified == modified && cached.size == size {
+                self.cache_stats.lock().unwrap().hits += 1;
+                return cached.hash.clone();
+            }
         }
-        
-        Ok(files)
+
+        let hash = hash_bytes(bytes);
+        cache.insert(path.to_path_buf(), CachedHash { modified, size, hash: hash.clone() });
+        self.cache_stats.lock().unwrap().misses += 1;
+        hash
     }
-    
-    
+
     fn find_active_session_file(&self) -> Result<PathBuf, ObserverError> {
-        let claude_projects = home::home_dir()
-            .ok_or_else(|| ObserverError::HomeNotFound)?
-            .join(".claude")
-            .join("projects");
-            
-        // Convert workspace path to Claude's project naming pattern
-        let project_name = encode_project_path(&self.workspace);
-            
-        let project_dir = claude_projects.join(&project_name);
-        
-        
-        if !project_dir.exists() {
-            return Err(ObserverError::ProjectNotFound(format!(
-                "Project directory not found: {:?} (encoded from workspace: {:?})", 
-                project_dir,
-                self.workspace
-            )));
-        }
-        
-        // Find most recent session file
-        let mut session_files: Vec<_> = std::fs::read_dir(&project_dir)
-            .map_err(|e| ObserverError::IoError(e.to_string()))?
-            .filter_map(|entry| entry.ok())
-            .filter(|entry| {
-                entry.path().extension()
-                    .map(|ext| ext == "jsonl")
-                    .unwrap_or(false)
-            })
-            .collect();
-            
-        session_files.sort_by_key(|entry| {
-            entry.metadata()
-                .and_then(|m| m.modified())
-                .unwrap_or(std::time::SystemTime::UNIX_EPOCH)
-        });
-        
-        session_files
-            .last()
-            .map(|entry| entry.path())
-            .ok_or_else(|| ObserverError::NoSessionFound)
+        self.locator.active_session_file(&self.workspace)
     }
-    
+
     fn find_session_file_by_id(&self, session_id: &str) -> Result<PathBuf, ObserverError> {
-        let claude_projects = home::home_dir()
-            .ok_or_else(|| ObserverError::HomeNotFound)?
-            .join(".claude")
-            .join("projects");
-            
-        // Convert workspace path to Claude's project naming pattern
-        let project_name = encode_project_path(&self.workspace);
-            
-        let project_dir = claude_projects.join(&project_name);
-        let session_file = project_dir.join(format!("{}.jsonl", session_id));
-        
-        if !session_file.exists() {
-            return Err(ObserverError::NoSessionFound);
-        }
-        
-        Ok(session_file)
+        self.locator.session_file_by_id(&self.workspace, session_id)
     }
 }
 
@@ -190,22 +646,126 @@ impl EnvironmentObserver {
 pub enum ObserverError {
     #[error("Glob pattern error: {0}")]
     GlobError(String),
-    
+
     #[error("Failed to parse session: {0}")]
     ParseError(String),
-    
+
     #[error("Home directory not found")]
     HomeNotFound,
-    
+
     #[error("Invalid workspace path")]
     InvalidWorkspace,
-    
+
     #[error("Project not found: {0}")]
     ProjectNotFound(String),
-    
+
     #[error("No session files found")]
     NoSessionFound,
-    
+
     #[error("IO error: {0}")]
     IoError(String),
+
+    #[error("Watch error: {0}")]
+    WatchError(String),
+}
+
+/// Handle to a running [`EnvironmentObserver::watch`]. Dropping it (or
+/// calling [`WatchHandle::stop`]) stops the filesystem watcher and joins its
+/// background thread.
+pub struct WatchHandle {
+    stop: Arc<AtomicBool>,
+    thread: Option<std::thread::JoinHandle<()>>,
+    _watcher: RecommendedWatcher,
+}
+
+impl WatchHandle {
+    /// Stop watching and wait for the background thread to exit.
+    pub fn stop(mut self) {
+        self.stop_inner();
+    }
+
+    fn stop_inner(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for WatchHandle {
+    fn drop(&mut self) {
+        self.stop_inner();
+    }
+}
+
+/// Build the include/exclude matcher [`EnvironmentObserver::watch`] checks
+/// raw filesystem events against: the workspace's top-level `.gitignore`
+/// plus `config.extra_ignores`. Nested `.gitignore`/`.ignore` files, global
+/// git excludes and `config.respect_gitignore` are not taken into account.
+fn build_ignore_matcher(workspace: &Path, config: &SnapshotConfig) -> Gitignore {
+    let mut builder = GitignoreBuilder::new(workspace);
+    let _ = builder.add(workspace.join(".gitignore"));
+    for pattern in &config.extra_ignores {
+        let _ = builder.add_line(None, pattern);
+    }
+    builder.build().unwrap_or_else(|_| Gitignore::empty())
+}
+
+/// Whether a raw notify event touches a path the observer actually tracks:
+/// inside the workspace, not hidden (dotfiles/`.git`), and not matched by
+/// `matcher`.
+fn event_is_relevant(matcher: &Gitignore, workspace: &Path, event: &notify::Event) -> bool {
+    event.paths.iter().any(|path| {
+        let relative = match path.strip_prefix(workspace) {
+            Ok(relative) => relative,
+            Err(_) => return false,
+        };
+        if relative.as_os_str().is_empty() {
+            return false;
+        }
+        let hidden = relative
+            .components()
+            .any(|c| c.as_os_str().to_str().map(|s| s.starts_with('.')).unwrap_or(false));
+        if hidden {
+            return false;
+        }
+        !matcher.matched(relative, path.is_dir()).is_ignore()
+    })
+}
+
+/// Stable content hash used to compare [`FileSnapshot`]s cheaply.
+fn hash_bytes(bytes: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(bytes);
+    format!("{:x}", hasher.finalize())
+}
+
+/// Whether `path`'s extension passes `config`'s allow/deny sets. A file with
+/// no extension passes the deny check but fails an active allowlist, since
+/// it can't be named in one.
+fn extension_allowed(path: &Path, config: &SnapshotConfig) -> bool {
+    let extension = path.extension().and_then(|ext| ext.to_str());
+
+    if let Some(extension) = extension {
+        if config.denied_extensions.contains(extension) {
+            return false;
+        }
+    }
+
+    match &config.allowed_extensions {
+        None => true,
+        Some(allowed) => extension.map(|ext| allowed.contains(ext)).unwrap_or(false),
+    }
+}
+
+/// Size, in bytes, peeked for [`looks_binary`]'s NUL-byte check.
+const BINARY_SNIFF_LEN: usize = 8 * 1024;
+
+/// Heuristic binary detection: a NUL byte anywhere in the first
+/// [`BINARY_SNIFF_LEN`] bytes marks the file as binary, avoiding a full
+/// UTF-8 validation pass (and the body we'd otherwise try to keep) for files
+/// that were never going to be valid text.
+fn looks_binary(bytes: &[u8]) -> bool {
+    let sniff_len = bytes.len().min(BINARY_SNIFF_LEN);
+    bytes[..sniff_len].contains(&0)
 }
\ No newline at end of file
@@ -0,0 +1,400 @@
+//! Structured, glob-scoped tool-permission model.
+//!
+//! Replaces raw `Option<String>` allow/deny lists (and the earlier
+//! unscoped [`Capability`] shape) with a small ACL: a [`Scope`] names a tool
+//! plus an optional argument glob (`Bash(git commit:*)`, `Read(src/**)`); a
+//! [`Permission`] bundles `allow`/`deny` scopes under a label; a
+//! [`Capability`] groups named permissions into a profile that can be
+//! serialized to TOML/JSON, attached to a [`crate::execution::Workspace`]
+//! via `set_permission_profile`, and evaluated directly with
+//! [`Capability::check`] without running Claude at all.
+
+use serde::{Deserialize, Serialize};
+
+use crate::execution::EnvironmentSnapshot;
+
+/// A tool invocation the static profile didn't explicitly allow or deny,
+/// handed to a [`super::executor::ClaudeExecutor`]'s permission handler so
+/// it can decide interactively instead of the model just being told "not
+/// allowed".
+#[derive(Debug, Clone)]
+pub struct ToolRequest {
+    pub tool: String,
+    pub args: String,
+    pub snapshot: EnvironmentSnapshot,
+}
+
+/// A permission handler's verdict on a [`ToolRequest`]. The `*AndRemember`
+/// variants additionally fold a matching scope into the profile so the same
+/// tool isn't re-prompted for the rest of the session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PermissionDecision {
+    Allow,
+    Deny,
+    AllowAndRemember,
+    DenyAndRemember,
+}
+
+/// A tool name plus an optional argument glob narrowing which invocations it
+/// matches. `Scope::new("Bash")` matches every `Bash` call unconditionally;
+/// `Scope::scoped("Bash", "git commit:*")` only matches invocations whose
+/// argument string matches that glob.
+///
+/// Globs are case-sensitive and support `*` (matches any run of characters,
+/// including none) matched against the whole argument string. `arg_glob` is
+/// an arbitrary shell-command/argument string, not a filesystem path, so
+/// there's no special treatment of `/` the way `.gitignore` patterns have.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Scope {
+    pub tool: String,
+    pub arg_glob: Option<String>,
+}
+
+impl Scope {
+    pub fn new(tool: impl Into<String>) -> Self {
+        Self {
+            tool: tool.into(),
+            arg_glob: None,
+        }
+    }
+
+    pub fn scoped(tool: impl Into<String>, arg_glob: impl Into<String>) -> Self {
+        Self {
+            tool: tool.into(),
+            arg_glob: Some(arg_glob.into()),
+        }
+    }
+
+    /// Whether this scope matches a tool invocation, given the raw argument
+    /// string Claude would have passed it (e.g. a shell command or file
+    /// path). A scope with no glob matches the tool unconditionally.
+    pub fn matches(&self, tool: &str, args: &str) -> bool {
+        if self.tool != tool {
+            return false;
+        }
+        match &self.arg_glob {
+            None => true,
+            Some(glob) => glob_match(glob, args),
+        }
+    }
+
+    /// Render in the `Tool` or `Tool(glob)` form the CLI's
+    /// `--allowedTools`/`--disallowedTools` flags expect.
+    pub fn to_flag_fragment(&self) -> String {
+        match &self.arg_glob {
+            Some(glob) => format!("{}({})", self.tool, glob),
+            None => self.tool.clone(),
+        }
+    }
+}
+
+/// A named bundle of allow/deny scopes, e.g. a `no-destructive-bash`
+/// permission that denies `Bash(rm -rf*)` while leaving everything else
+/// untouched. Deny always takes precedence over allow within (and across)
+/// permissions — see [`Capability::check`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Permission {
+    pub identifier: String,
+    #[serde(default)]
+    pub allow: Vec<Scope>,
+    #[serde(default)]
+    pub deny: Vec<Scope>,
+}
+
+impl Permission {
+    pub fn new(identifier: impl Into<String>) -> Self {
+        Self {
+            identifier: identifier.into(),
+            allow: Vec::new(),
+            deny: Vec::new(),
+        }
+    }
+
+    pub fn with_allow(mut self, scope: Scope) -> Self {
+        self.allow.push(scope);
+        self
+    }
+
+    pub fn with_deny(mut self, scope: Scope) -> Self {
+        self.deny.push(scope);
+        self
+    }
+}
+
+/// A named, reusable permission profile, serializable to/from TOML or JSON
+/// and attached to a [`crate::execution::Workspace`] via
+/// `set_permission_profile`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Capability {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub permissions: Vec<Permission>,
+}
+
+/// Outcome of evaluating a tool invocation against a [`Capability`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Decision {
+    Allowed,
+    Denied,
+}
+
+impl Decision {
+    pub fn is_allowed(self) -> bool {
+        matches!(self, Decision::Allowed)
+    }
+}
+
+impl Capability {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            description: String::new(),
+            permissions: Vec::new(),
+        }
+    }
+
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = description.into();
+        self
+    }
+
+    pub fn with_permission(mut self, permission: Permission) -> Self {
+        self.permissions.push(permission);
+        self
+    }
+
+    /// Build a single-permission capability equivalent to the legacy
+    /// comma-separated `--allowedTools`/`--disallowedTools` strings, so
+    /// `Workspace::set_allowed_tools`/`set_disallowed_tools` keep working
+    /// unchanged on top of the structured model.
+    pub fn from_tool_lists(allowed: Option<&str>, disallowed: Option<&str>) -> Self {
+        let parse = |list: &str| -> Vec<Scope> {
+            list.split(',')
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(parse_flag_fragment)
+                .collect()
+        };
+
+        let mut permission = Permission::new("legacy-tool-lists");
+        if let Some(allowed) = allowed {
+            permission.allow = parse(allowed);
+        }
+        if let Some(disallowed) = disallowed {
+            permission.deny = parse(disallowed);
+        }
+
+        Capability::new("legacy-tool-lists").with_permission(permission)
+    }
+
+    /// Whether any permission in this capability carries an explicit allow
+    /// scope. When none do, an unmatched-by-deny tool is allowed by default
+    /// (see [`Capability::check`]).
+    fn has_explicit_allow(&self) -> bool {
+        self.permissions.iter().any(|p| !p.allow.is_empty())
+    }
+
+    /// Evaluate a tool invocation against this profile. Deny takes
+    /// precedence over allow; a capability with no explicit allow scopes
+    /// (only denies) allows everything it doesn't deny.
+    pub fn check(&self, tool: &str, args: &str) -> Decision {
+        let denied = self
+            .permissions
+            .iter()
+            .flat_map(|p| &p.deny)
+            .any(|scope| scope.matches(tool, args));
+        if denied {
+            return Decision::Denied;
+        }
+
+        if !self.has_explicit_allow() {
+            return Decision::Allowed;
+        }
+
+        let allowed = self
+            .permissions
+            .iter()
+            .flat_map(|p| &p.allow)
+            .any(|scope| scope.matches(tool, args));
+
+        if allowed {
+            Decision::Allowed
+        } else {
+            Decision::Denied
+        }
+    }
+
+    /// Whether any scope (allow or deny) in this profile matches the
+    /// invocation, i.e. whether `check` reflects an explicit rule rather
+    /// than the "allow by default" fallback.
+    pub fn is_covered(&self, tool: &str, args: &str) -> bool {
+        self.permissions
+            .iter()
+            .flat_map(|p| p.allow.iter().chain(p.deny.iter()))
+            .any(|scope| scope.matches(tool, args))
+    }
+
+    /// Fold `tool` into this profile's allow or deny list under a dedicated
+    /// `remembered` permission, so future `check`/`is_covered` calls treat it
+    /// as explicitly covered. Used to apply `AllowAndRemember`/
+    /// `DenyAndRemember` decisions from a permission handler.
+    pub fn remember(&mut self, tool: impl Into<String>, allow: bool) {
+        let permission = match self.permissions.iter_mut().find(|p| p.identifier == "remembered") {
+            Some(p) => p,
+            None => {
+                self.permissions.push(Permission::new("remembered"));
+                self.permissions.last_mut().unwrap()
+            }
+        };
+        let scope = Scope::new(tool);
+        if allow {
+            permission.allow.push(scope);
+        } else {
+            permission.deny.push(scope);
+        }
+    }
+
+    /// Compile to the `(--allowedTools, --disallowedTools)` flag values.
+    pub fn to_cli_flags(&self) -> (Option<String>, Option<String>) {
+        let allow: Vec<String> = self
+            .permissions
+            .iter()
+            .flat_map(|p| &p.allow)
+            .map(Scope::to_flag_fragment)
+            .collect();
+        let deny: Vec<String> = self
+            .permissions
+            .iter()
+            .flat_map(|p| &p.deny)
+            .map(Scope::to_flag_fragment)
+            .collect();
+
+        (
+            (!allow.is_empty()).then(|| allow.join(",")),
+            (!deny.is_empty()).then(|| deny.join(",")),
+        )
+    }
+
+    pub fn to_toml(&self) -> Result<String, PermissionError> {
+        toml::to_string_pretty(self).map_err(|e| PermissionError::Serialize(e.to_string()))
+    }
+
+    pub fn from_toml(text: &str) -> Result<Self, PermissionError> {
+        toml::from_str(text).map_err(|e| PermissionError::Deserialize(e.to_string()))
+    }
+
+    pub fn to_json(&self) -> Result<String, PermissionError> {
+        serde_json::to_string_pretty(self).map_err(|e| PermissionError::Serialize(e.to_string()))
+    }
+
+    pub fn from_json(text: &str) -> Result<Self, PermissionError> {
+        serde_json::from_str(text).map_err(|e| PermissionError::Deserialize(e.to_string()))
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum PermissionError {
+    #[error("failed to serialize permission profile: {0}")]
+    Serialize(String),
+
+    #[error("failed to parse permission profile: {0}")]
+    Deserialize(String),
+}
+
+/// Parse a single `Tool` or `Tool(glob)` flag fragment, as produced by
+/// [`Scope::to_flag_fragment`], back into a [`Scope`].
+fn parse_flag_fragment(fragment: &str) -> Scope {
+    match fragment.split_once('(') {
+        Some((tool, rest)) if rest.ends_with(')') => {
+            Scope::scoped(tool, &rest[..rest.len() - 1])
+        }
+        _ => Scope::new(fragment),
+    }
+}
+
+/// Match `text` against a single-wildcard glob: `*` matches any run of
+/// characters, including none, with backtracking so e.g. `git commit:*`
+/// matches `"git commit -m 'fix'"` even though nothing in the text matches
+/// the literal `:`. `arg_glob` is an arbitrary argument/shell-command
+/// string, not a filesystem path — unlike `.gitignore` patterns, `/` has no
+/// special meaning and the whole string is matched as one unit.
+fn glob_match(pattern: &str, text: &str) -> bool {
+    fn helper(pattern: &[u8], text: &[u8]) -> bool {
+        match pattern.first() {
+            None => text.is_empty(),
+            Some(b'*') => helper(&pattern[1..], text) || (!text.is_empty() && helper(pattern, &text[1..])),
+            // A `:` immediately before a `*` is Claude's own prefix/wildcard
+            // separator (`Bash(git commit:*)`), not a literal character to
+            // match — drop it from the pattern without consuming any text,
+            // so `"git commit:*"` matches `"git commit -m 'fix'"` even
+            // though nothing in the text is a literal colon.
+            Some(b':') if pattern.get(1) == Some(&b'*') => helper(&pattern[1..], text),
+            Some(p) => match text.first() {
+                Some(t) if p == t => helper(&pattern[1..], &text[1..]),
+                _ => false,
+            },
+        }
+    }
+    helper(pattern.as_bytes(), text.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bash_colon_prefix_glob_matches_real_command_line() {
+        let scope = Scope::scoped("Bash", "git commit:*");
+        assert!(scope.matches("Bash", "git commit -m 'fix'"));
+        assert!(!scope.matches("Bash", "git push"));
+    }
+
+    #[test]
+    fn glob_with_file_path_argument_is_matched_as_one_string() {
+        // A deny glob for a destructive command must still match once a
+        // real file path (which contains `/`) is appended as an argument —
+        // these are shell-command strings, not filesystem paths, so there's
+        // no `/`-segmentation to trip over.
+        let scope = Scope::scoped("Bash", "rm -rf*");
+        assert!(scope.matches("Bash", "rm -rf /tmp/foo"));
+        assert!(scope.matches("Bash", "rm -rf /"));
+        assert!(!scope.matches("Bash", "rm /tmp/foo"));
+    }
+
+    #[test]
+    fn unscoped_scope_matches_tool_regardless_of_args() {
+        let scope = Scope::new("Bash");
+        assert!(scope.matches("Bash", "anything at all"));
+        assert!(!scope.matches("Read", "anything at all"));
+    }
+
+    #[test]
+    fn star_glob_backtracks_across_the_whole_string() {
+        assert!(glob_match("*foo*", "a foo b"));
+        assert!(glob_match("foo*bar", "foobar"));
+        assert!(glob_match("foo*bar", "foo---bar"));
+        assert!(!glob_match("foo*bar", "foobaz"));
+    }
+
+    #[test]
+    fn literal_colon_not_before_star_still_matches_literally() {
+        assert!(glob_match("a:b", "a:b"));
+        assert!(!glob_match("a:b", "a b"));
+    }
+
+    #[test]
+    fn deny_takes_precedence_over_allow_for_matching_destructive_command() {
+        let permission = Permission::new("no-destructive-bash")
+            .with_allow(Scope::new("Bash"))
+            .with_deny(Scope::scoped("Bash", "rm -rf*"));
+        let capability = Capability::new("profile").with_permission(permission);
+
+        assert_eq!(
+            capability.check("Bash", "rm -rf /tmp/foo"),
+            Decision::Denied
+        );
+        assert_eq!(capability.check("Bash", "ls -la"), Decision::Allowed);
+    }
+}
@@ -1,13 +1,91 @@
+use std::collections::HashSet;
 use std::fs::{create_dir_all, OpenOptions};
 use std::io::Write;
 use std::path::{Path, PathBuf};
 use uuid::Uuid;
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
+use similar::{ChangeTag, TextDiff};
+use crate::execution::blob_store::{self, CasStore};
 use crate::execution::{ClaudePrompt, ClaudeExecution, EnvironmentSnapshot};
 use crate::types::{MessageRecord, ContentBlock, ToolExecution, ToolResult as TypesToolResult};
 use std::time::Duration;
 
+/// Tool names whose `input` carries a `file_path` argument, used by
+/// [`Transition::touched_paths`] to infer filesystem impact even when
+/// snapshot bodies were elided.
+const FILE_WRITING_TOOLS: &[&str] = &["Edit", "MultiEdit", "Write", "NotebookEdit"];
+
+/// How a path's content changed between a transition's `before` and `after`
+/// snapshots.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ChangeKind {
+    Added,
+    Modified,
+    Deleted,
+    /// A path was deleted and a different path with identical content
+    /// appeared in the same transition; only reported by [`Transition::diff`].
+    Renamed,
+}
+
+/// A single contiguous edit within a unified line diff.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiffHunk {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+/// One path's change between a transition's `before` and `after` snapshots,
+/// computed by set-differencing `EnvironmentSnapshot::files`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileChange {
+    pub path: PathBuf,
+    /// The path this one was renamed from, populated only for
+    /// `ChangeKind::Renamed`.
+    #[serde(default)]
+    pub old_path: Option<PathBuf>,
+    pub kind: ChangeKind,
+    pub old_hash: Option<String>,
+    pub new_hash: Option<String>,
+    /// Line-level unified diff, populated only for `Modified` changes where
+    /// both snapshots retained file bodies.
+    pub diff: Option<Vec<DiffHunk>>,
+}
+
+/// [`Transition::diff`]'s changes grouped by kind instead of left as a flat
+/// list: which paths were added, which were removed (including the old side
+/// of a rename), and which were modified, paired with their old and new
+/// content hash.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SnapshotDiff {
+    pub added: Vec<PathBuf>,
+    pub removed: Vec<PathBuf>,
+    pub modified: Vec<(PathBuf, String, String)>,
+}
+
+/// Structured payload for [`Transition::metadata`]: anything recorded about
+/// a transition beyond its core prompt/execution/snapshot fields, typed
+/// instead of left as an untyped JSON blob every consumer has to dig through.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TransitionMetadata {
+    /// Paths the filesystem watcher saw change while this transition's
+    /// prompt was executing. See `ClaudeEnvironment::execute_with_options`.
+    #[serde(default)]
+    pub changed_paths: Vec<PathBuf>,
+    /// Set when this transition was recorded by `ClaudeEnvironment::fork`:
+    /// the id of the transition it branched from, so [`TransitionRecorder::tree`]
+    /// can reconstruct a branching history from an otherwise flat log.
+    #[serde(default)]
+    pub parent: Option<Uuid>,
+    /// Set when `Conversation::send` found the prior turn's session ID no
+    /// longer accepted by Claude and transparently restarted a fresh
+    /// session to keep the conversation usable: holds the stale session ID
+    /// that was replaced, so callers can tell this transition's
+    /// `execution.session_id` doesn't actually continue the one before it.
+    #[serde(default)]
+    pub session_recovered_from: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Transition {
     pub id: Uuid,
@@ -17,82 +95,20 @@ pub struct Transition {
     pub after: EnvironmentSnapshot,
     pub recorded_at: DateTime<Utc>,
     #[serde(default)]
-    pub metadata: serde_json::Value,
+    pub metadata: TransitionMetadata,
 }
 
 impl Transition {
     /// Get the new messages added in this transition
     pub fn new_messages(&self) -> Vec<&MessageRecord> {
-        match (&self.before.session, &self.after.session) {
-            (Some(before_session), Some(after_session)) => {
-                let before_count = before_session.messages.len();
-                after_session.messages.iter()
-                    .skip(before_count)
-                    .collect()
-            }
-            (None, Some(after_session)) => {
-                // First execution, all messages are new
-                after_session.messages.iter().collect()
-            }
-            _ => Vec::new(),
-        }
+        new_messages_between(&self.before, &self.after)
     }
-    
+
     /// Extract tool executions from this transition
     pub fn tool_executions(&self) -> Vec<ToolExecution> {
-        let mut executions = Vec::new();
-        let new_messages = self.new_messages();
-        
-        // Track tool uses waiting for results
-        let mut pending_tools: std::collections::HashMap<String, (String, serde_json::Value, DateTime<Utc>)> = 
-            std::collections::HashMap::new();
-        
-        for message in new_messages {
-            for content in &message.message.content {
-                match content {
-                    ContentBlock::ToolUse { id, name, input } => {
-                        // Record tool use
-                        pending_tools.insert(
-                            id.clone(), 
-                            (name.clone(), input.clone(), message.timestamp)
-                        );
-                    }
-                    ContentBlock::ToolResult { tool_use_id, content, is_error } => {
-                        // Match with tool use
-                        if let Some((tool_name, input, start_time)) = pending_tools.remove(tool_use_id) {
-                            let duration = message.timestamp.signed_duration_since(start_time)
-                                .to_std()
-                                .unwrap_or(Duration::from_secs(0));
-                            
-                            let tool_result = TypesToolResult {
-                                tool_use_id: tool_use_id.clone(),
-                                content: content.as_ref()
-                                    .map(|c| c.as_text())
-                                    .unwrap_or_default(),
-                                stdout: None,  // Could parse from content
-                                stderr: None,
-                                interrupted: false,
-                                is_error: is_error.unwrap_or(false),
-                                metadata: serde_json::Value::Null,
-                            };
-                            
-                            executions.push(ToolExecution::new(
-                                tool_name,
-                                input,
-                                tool_result,
-                                duration,
-                                start_time,
-                            ));
-                        }
-                    }
-                    _ => {}
-                }
-            }
-        }
-        
-        executions
+        tool_executions_between(&self.before, &self.after)
     }
-    
+
     /// Get just the tool names used in this transition
     pub fn tools_used(&self) -> Vec<String> {
         self.tool_executions()
@@ -107,11 +123,348 @@ impl Transition {
             .iter()
             .any(|exec| !exec.is_success())
     }
+
+    /// Set-difference `before.files` against `after.files` by hash, yielding
+    /// one [`FileChange`] per path that was added, modified, or deleted.
+    pub fn file_changes(&self) -> Vec<FileChange> {
+        diff_snapshots(&self.before, &self.after)
+    }
+
+    /// Like [`Transition::file_changes`], but additionally collapses an
+    /// `Added`/`Deleted` pair that share a content hash into a single
+    /// `Renamed` change, so moving a file without editing it doesn't show up
+    /// as an unrelated delete-and-add.
+    pub fn diff(&self) -> Vec<FileChange> {
+        let mut added = Vec::new();
+        let mut deleted = Vec::new();
+        let mut result = Vec::new();
+
+        for change in self.file_changes() {
+            match change.kind {
+                ChangeKind::Added => added.push(change),
+                ChangeKind::Deleted => deleted.push(change),
+                _ => result.push(change),
+            }
+        }
+
+        for add in added {
+            let rename_from = deleted
+                .iter()
+                .position(|del| del.old_hash.is_some() && del.old_hash == add.new_hash);
+
+            match rename_from {
+                Some(index) => {
+                    let del = deleted.remove(index);
+                    result.push(FileChange {
+                        path: add.path,
+                        old_path: Some(del.path),
+                        kind: ChangeKind::Renamed,
+                        old_hash: del.old_hash,
+                        new_hash: add.new_hash,
+                        diff: None,
+                    });
+                }
+                None => result.push(add),
+            }
+        }
+        result.extend(deleted);
+
+        result
+    }
+
+    /// Paths added or modified during this transition.
+    pub fn files_written(&self) -> Vec<PathBuf> {
+        self.file_changes()
+            .into_iter()
+            .filter(|c| matches!(c.kind, ChangeKind::Added | ChangeKind::Modified))
+            .map(|c| c.path)
+            .collect()
+    }
+
+    /// Paths removed during this transition.
+    pub fn files_deleted(&self) -> Vec<PathBuf> {
+        self.file_changes()
+            .into_iter()
+            .filter(|c| c.kind == ChangeKind::Deleted)
+            .map(|c| c.path)
+            .collect()
+    }
+
+    /// Group this transition's [`Transition::diff`] output into
+    /// [`SnapshotDiff`]'s added/removed/modified buckets, instead of a flat
+    /// list callers have to filter by `kind` themselves. A `Renamed` change
+    /// is split into a removal of its `old_path` plus an add of its new one.
+    pub fn snapshot_diff(&self) -> SnapshotDiff {
+        let mut diff = SnapshotDiff::default();
+
+        for change in self.diff() {
+            match change.kind {
+                ChangeKind::Added => diff.added.push(change.path),
+                ChangeKind::Deleted => diff.removed.push(change.path),
+                ChangeKind::Renamed => {
+                    if let Some(old_path) = change.old_path {
+                        diff.removed.push(old_path);
+                    }
+                    diff.added.push(change.path);
+                }
+                ChangeKind::Modified => {
+                    if let (Some(old_hash), Some(new_hash)) = (change.old_hash, change.new_hash) {
+                        diff.modified.push((change.path, old_hash, new_hash));
+                    }
+                }
+            }
+        }
+
+        diff
+    }
+
+    /// Union of `file_changes()` paths with paths inferred from file-writing
+    /// tool calls (Edit/Write/MultiEdit/NotebookEdit inputs), so a turn's full
+    /// filesystem impact is visible even when snapshot bodies were elided.
+    pub fn touched_paths(&self) -> Vec<PathBuf> {
+        let mut paths: HashSet<PathBuf> = self
+            .file_changes()
+            .into_iter()
+            .map(|c| c.path)
+            .collect();
+
+        for execution in self.tool_executions() {
+            if !FILE_WRITING_TOOLS.contains(&execution.tool_name.as_str()) {
+                continue;
+            }
+            if let Some(path) = execution
+                .input
+                .get("file_path")
+                .and_then(|v| v.as_str())
+            {
+                paths.insert(PathBuf::from(path));
+            }
+        }
+
+        let mut paths: Vec<PathBuf> = paths.into_iter().collect();
+        paths.sort();
+        paths
+    }
+}
+
+/// Messages present in `after` but not `before`, by session message count.
+/// Shared by [`Transition::new_messages`] and
+/// [`super::observer::EnvironmentObserver::watch_changes`], which diffs
+/// consecutive live snapshots rather than a whole turn's before/after pair.
+pub(crate) fn new_messages_between<'a>(
+    before: &'a EnvironmentSnapshot,
+    after: &'a EnvironmentSnapshot,
+) -> Vec<&'a MessageRecord> {
+    match (&before.session, &after.session) {
+        (Some(before_session), Some(after_session)) => {
+            let before_count = before_session.messages.len();
+            after_session.messages.iter()
+                .skip(before_count)
+                .collect()
+        }
+        (None, Some(after_session)) => {
+            // First execution, all messages are new
+            after_session.messages.iter().collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Pair up `ToolUse`/`ToolResult` blocks across the messages new to `after`
+/// since `before`, the same tool-extraction logic
+/// [`Transition::tool_executions`] runs over a whole turn.
+pub(crate) fn tool_executions_between(
+    before: &EnvironmentSnapshot,
+    after: &EnvironmentSnapshot,
+) -> Vec<ToolExecution> {
+    let mut executions = Vec::new();
+    let new_messages = new_messages_between(before, after);
+
+    // Track tool uses waiting for results
+    let mut pending_tools: std::collections::HashMap<String, (String, serde_json::Value, DateTime<Utc>)> =
+        std::collections::HashMap::new();
+
+    for message in new_messages {
+        for content in &message.message.content {
+            match content {
+                ContentBlock::ToolUse { id, name, input } => {
+                    // Record tool use
+                    pending_tools.insert(
+                        id.clone(),
+                        (name.clone(), input.clone(), message.timestamp)
+                    );
+                }
+                ContentBlock::ToolResult { tool_use_id, content, is_error } => {
+                    // Match with tool use
+                    if let Some((tool_name, input, start_time)) = pending_tools.remove(tool_use_id) {
+                        let duration = message.timestamp.signed_duration_since(start_time)
+                            .to_std()
+                            .unwrap_or(Duration::from_secs(0));
+
+                        let tool_result = TypesToolResult {
+                            tool_use_id: tool_use_id.clone(),
+                            content: content.as_ref()
+                                .map(|c| c.as_text())
+                                .unwrap_or_default(),
+                            stdout: None,  // Could parse from content
+                            stderr: None,
+                            interrupted: false,
+                            is_error: is_error.unwrap_or(false),
+                            metadata: serde_json::Value::Null,
+                        };
+
+                        executions.push(ToolExecution::new(
+                            tool_name,
+                            input,
+                            tool_result,
+                            duration,
+                            start_time,
+                        ));
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    executions
+}
+
+/// Set-difference `before.files` against `after.files` by hash, yielding one
+/// [`FileChange`] per path that was added, modified, or deleted. Shared by
+/// [`Transition::file_changes`] and [`super::observer::EnvironmentObserver::watch_during`],
+/// which diffs a pair of snapshots bracketing an arbitrary closure rather
+/// than a whole turn.
+pub(crate) fn diff_snapshots(
+    before: &EnvironmentSnapshot,
+    after: &EnvironmentSnapshot,
+) -> Vec<FileChange> {
+    let mut changes = Vec::new();
+    let before = &before.files;
+    let after = &after.files;
+
+    for (path, after_file) in after {
+        match before.get(path) {
+            None => changes.push(FileChange {
+                path: path.clone(),
+                old_path: None,
+                kind: ChangeKind::Added,
+                old_hash: None,
+                new_hash: Some(after_file.hash.clone()),
+                diff: None,
+            }),
+            Some(before_file) if before_file.hash != after_file.hash => {
+                let diff = match (&before_file.body, &after_file.body) {
+                    (Some(old), Some(new)) => Some(diff_lines(old, new)),
+                    _ => None,
+                };
+                changes.push(FileChange {
+                    path: path.clone(),
+                    old_path: None,
+                    kind: ChangeKind::Modified,
+                    old_hash: Some(before_file.hash.clone()),
+                    new_hash: Some(after_file.hash.clone()),
+                    diff,
+                });
+            }
+            Some(_) => {} // unchanged
+        }
+    }
+
+    for (path, before_file) in before {
+        if !after.contains_key(path) {
+            changes.push(FileChange {
+                path: path.clone(),
+                old_path: None,
+                kind: ChangeKind::Deleted,
+                old_hash: Some(before_file.hash.clone()),
+                new_hash: None,
+                diff: None,
+            });
+        }
+    }
+
+    changes
+}
+
+/// Compute a simple unified line diff between `old` and `new`, grouping
+/// consecutive insertions/deletions into [`DiffHunk`]s.
+fn diff_lines(old: &str, new: &str) -> Vec<DiffHunk> {
+    let diff = TextDiff::from_lines(old, new);
+    let mut hunks = Vec::new();
+    let mut current: Option<DiffHunk> = None;
+
+    for change in diff.iter_all_changes() {
+        match change.tag() {
+            ChangeTag::Equal => {
+                if let Some(hunk) = current.take() {
+                    hunks.push(hunk);
+                }
+            }
+            ChangeTag::Delete => {
+                current
+                    .get_or_insert_with(|| DiffHunk {
+                        added: Vec::new(),
+                        removed: Vec::new(),
+                    })
+                    .removed
+                    .push(change.to_string());
+            }
+            ChangeTag::Insert => {
+                current
+                    .get_or_insert_with(|| DiffHunk {
+                        added: Vec::new(),
+                        removed: Vec::new(),
+                    })
+                    .added
+                    .push(change.to_string());
+            }
+        }
+    }
+    if let Some(hunk) = current.take() {
+        hunks.push(hunk);
+    }
+
+    hunks
+}
+
+/// One row of the sidecar index: enough to seek straight to a transition's
+/// bytes and to answer common queries without deserializing its body.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IndexEntry {
+    id: Uuid,
+    file: PathBuf,
+    byte_offset: u64,
+    recorded_at: DateTime<Utc>,
+    session_id: Option<String>,
+    tools_used: Vec<String>,
+    has_errors: bool,
+}
+
+impl IndexEntry {
+    fn from_transition(transition: &Transition, file: PathBuf, byte_offset: u64) -> Self {
+        Self {
+            id: transition.id,
+            file,
+            byte_offset,
+            recorded_at: transition.recorded_at,
+            session_id: Some(transition.execution.session_id.clone()),
+            tools_used: transition.tools_used(),
+            has_errors: transition.has_tool_errors(),
+        }
+    }
 }
 
 pub struct TransitionRecorder {
     storage_dir: PathBuf,
     current_session_file: PathBuf,
+    index_file: PathBuf,
+    /// Where this recorder's file bodies actually live on disk; every
+    /// transition written through [`record_impl`](Self::record_impl) has its
+    /// inline bodies stripped and pushed here instead, so the JSONL log
+    /// holds only hashes. Shared with [`super::Conversation::save`]'s own
+    /// `CasStore`, since both key off the same workspace.
+    cas: CasStore,
 }
 
 impl TransitionRecorder {
@@ -120,97 +473,271 @@ impl TransitionRecorder {
         let storage_dir = workspace.join(".claude-sdk").join("transitions");
         create_dir_all(&storage_dir)
             .map_err(|e| RecorderError::StorageError(e.to_string()))?;
-            
+
         let session_id = Uuid::new_v4();
         let current_session_file = storage_dir.join(format!("{}.jsonl", session_id));
-        
+        let index_file = storage_dir.join("index.jsonl");
+
         Ok(Self {
             storage_dir,
             current_session_file,
+            index_file,
+            cas: CasStore::new(workspace),
         })
     }
-    
-    pub fn record(&mut self, mut transition: Transition) -> Result<(), RecorderError> {
+
+    pub fn record(&mut self, transition: Transition) -> Result<(), RecorderError> {
+        self.record_impl(transition, false)
+    }
+
+    /// Like [`record`](Self::record), but for callers who need a hard
+    /// guarantee the transition survived: the write is `fsync`'d before
+    /// returning and then read back by id to confirm it's actually on disk,
+    /// not just buffered. Used by [`super::Conversation`] under
+    /// [`super::RecordingPolicy::Required`], where a caller relying on a
+    /// complete audit trail can't tolerate a silently lost write.
+    pub fn record_durable(&mut self, transition: Transition) -> Result<(), RecorderError> {
+        self.record_impl(transition, true)
+    }
+
+    fn record_impl(&mut self, mut transition: Transition, durable: bool) -> Result<(), RecorderError> {
         // Only set ID if not already set
         if transition.id == Uuid::nil() {
             transition.id = Uuid::new_v4();
         }
         transition.recorded_at = Utc::now();
-        
+        let id = transition.id;
+
+        // Strip inline file bodies out to the workspace's CAS so the JSONL
+        // log carries hashes only; `read_transition_at` refills them from
+        // the same store on the way back out.
+        let (mut stripped, blobs) = blob_store::extract_blobs(std::slice::from_ref(&transition));
+        self.cas
+            .write_all(&blobs)
+            .map_err(|e| RecorderError::StorageError(e.to_string()))?;
+        let transition = stripped.pop().expect("extract_blobs preserves length");
+
         let json = serde_json::to_string(&transition)
             .map_err(|e| RecorderError::SerializeError(e.to_string()))?;
-            
+
         let mut file = OpenOptions::new()
             .create(true)
             .append(true)
             .open(&self.current_session_file)
             .map_err(|e| RecorderError::StorageError(e.to_string()))?;
-            
+
+        let byte_offset = file
+            .metadata()
+            .map_err(|e| RecorderError::StorageError(e.to_string()))?
+            .len();
+
         writeln!(file, "{}", json)
             .map_err(|e| RecorderError::StorageError(e.to_string()))?;
-            
+
+        if durable {
+            file.sync_all()
+                .map_err(|e| RecorderError::StorageError(e.to_string()))?;
+        }
+
+        self.append_index_entry(&IndexEntry::from_transition(
+            &transition,
+            self.current_session_file.clone(),
+            byte_offset,
+        ))?;
+
+        if durable {
+            match self.load(id) {
+                Ok(Some(_)) => {}
+                Ok(None) => {
+                    return Err(RecorderError::StorageError(format!(
+                        "transition {} was written but did not verify on re-read",
+                        id
+                    )))
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
         Ok(())
     }
-    
+
+    /// Direct-seek lookup of a single transition by id via the index.
     pub fn load(&self, id: Uuid) -> Result<Option<Transition>, RecorderError> {
-        // Search through all transition files
-        for entry in std::fs::read_dir(&self.storage_dir)
-            .map_err(|e| RecorderError::StorageError(e.to_string()))? {
-            
-            let path = entry
-                .map_err(|e| RecorderError::StorageError(e.to_string()))?
-                .path();
-                
-            if path.extension().map(|e| e == "jsonl").unwrap_or(false) {
-                let content = std::fs::read_to_string(&path)
-                    .map_err(|e| RecorderError::StorageError(e.to_string()))?;
-                    
-                for line in content.lines() {
-                    if let Ok(transition) = serde_json::from_str::<Transition>(line) {
-                        if transition.id == id {
-                            return Ok(Some(transition));
-                        }
-                    }
-                }
+        for entry in self.read_index()? {
+            if entry.id == id {
+                return self.read_transition_at(&entry.file, entry.byte_offset).map(Some);
             }
         }
-        
         Ok(None)
     }
-    
+
+    /// The newest `limit` transitions without deserializing the rest of the log.
     pub fn recent(&self, limit: Option<usize>) -> Result<Vec<Transition>, RecorderError> {
-        let mut transitions = Vec::new();
-        
-        // Read all session log files
+        let mut entries = self.read_index()?;
+        entries.sort_by(|a, b| b.recorded_at.cmp(&a.recorded_at));
+        if let Some(limit) = limit {
+            entries.truncate(limit);
+        }
+        entries
+            .iter()
+            .map(|entry| self.read_transition_at(&entry.file, entry.byte_offset))
+            .collect()
+    }
+
+    /// All transitions recorded under the given Claude session id.
+    pub fn by_session(&self, session_id: &str) -> Result<Vec<Transition>, RecorderError> {
+        self.load_matching(|entry| entry.session_id.as_deref() == Some(session_id))
+    }
+
+    /// All transitions recorded between `from` and `to` (inclusive).
+    pub fn in_time_range(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<Transition>, RecorderError> {
+        self.load_matching(|entry| entry.recorded_at >= from && entry.recorded_at <= to)
+    }
+
+    /// All transitions that used the named tool at least once.
+    pub fn with_tool(&self, name: &str) -> Result<Vec<Transition>, RecorderError> {
+        self.load_matching(|entry| entry.tools_used.iter().any(|t| t == name))
+    }
+
+    /// All transitions where at least one tool call failed.
+    pub fn failed_only(&self) -> Result<Vec<Transition>, RecorderError> {
+        self.load_matching(|entry| entry.has_errors)
+    }
+
+    /// All transitions reachable from `root` by following `metadata.parent`
+    /// pointers forward — `root` itself plus every transition forked from it,
+    /// directly or transitively — in `recorded_at` order. Lets a caller
+    /// explore the branching history [`super::environment::ClaudeEnvironment::fork`]
+    /// produces from any recorded point, rather than assuming the single
+    /// linear line `recent`/`by_session` do.
+    pub fn tree(&self, root: Uuid) -> Result<Vec<Transition>, RecorderError> {
+        let all = self.load_matching(|_| true)?;
+
+        let mut result = Vec::new();
+        let mut frontier = vec![root];
+        while let Some(id) = frontier.pop() {
+            if let Some(transition) = all.iter().find(|t| t.id == id) {
+                result.push(transition.clone());
+            }
+            for child in &all {
+                if child.metadata.parent == Some(id) {
+                    frontier.push(child.id);
+                }
+            }
+        }
+
+        result.sort_by_key(|t| t.recorded_at);
+        Ok(result)
+    }
+
+    /// Rebuild `index.jsonl` from scratch by scanning every transition log in
+    /// `storage_dir`. Lets stores written before the index existed migrate
+    /// cleanly. The log does not store parsed sessions, so rebuilt entries
+    /// have an empty `tools_used` and `has_errors: false`, and
+    /// [`with_tool`](Self::with_tool)/[`failed_only`](Self::failed_only)
+    /// don't match them.
+    pub fn rebuild_index(&self) -> Result<(), RecorderError> {
+        let mut entries = Vec::new();
+
         for entry in std::fs::read_dir(&self.storage_dir)
             .map_err(|e| RecorderError::StorageError(e.to_string()))?
         {
-            let path = match entry {
-                Ok(e) => e.path(),
-                Err(e) => return Err(RecorderError::StorageError(e.to_string())),
-            };
-
-            if path.extension().map(|e| e == "jsonl").unwrap_or(false) {
-                let content = std::fs::read_to_string(&path)
-                    .map_err(|e| RecorderError::StorageError(e.to_string()))?;
-
-                for line in content.lines() {
-                    if let Ok(transition) = serde_json::from_str::<Transition>(line) {
-                        transitions.push(transition);
-                    }
+            let path = entry
+                .map_err(|e| RecorderError::StorageError(e.to_string()))?
+                .path();
+
+            if path == self.index_file || path.extension().map(|e| e != "jsonl").unwrap_or(true) {
+                continue;
+            }
+
+            let content = std::fs::read_to_string(&path)
+                .map_err(|e| RecorderError::StorageError(e.to_string()))?;
+
+            let mut byte_offset: u64 = 0;
+            for line in content.lines() {
+                if let Ok(transition) = serde_json::from_str::<Transition>(line) {
+                    entries.push(IndexEntry::from_transition(&transition, path.clone(), byte_offset));
                 }
+                byte_offset += line.len() as u64 + 1; // account for the stripped '\n'
             }
         }
-        
-        // Sort by timestamp (newest first)
-        transitions.sort_by(|a, b| b.recorded_at.cmp(&a.recorded_at));
-        
-        // Apply limit if specified
-        if let Some(limit) = limit {
-            transitions.truncate(limit);
+
+        let mut index_file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(&self.index_file)
+            .map_err(|e| RecorderError::StorageError(e.to_string()))?;
+
+        for entry in &entries {
+            let json = serde_json::to_string(entry)
+                .map_err(|e| RecorderError::SerializeError(e.to_string()))?;
+            writeln!(index_file, "{}", json).map_err(|e| RecorderError::StorageError(e.to_string()))?;
         }
-        
-        Ok(transitions)
+
+        Ok(())
+    }
+
+    fn load_matching(
+        &self,
+        predicate: impl Fn(&IndexEntry) -> bool,
+    ) -> Result<Vec<Transition>, RecorderError> {
+        self.read_index()?
+            .iter()
+            .filter(|entry| predicate(entry))
+            .map(|entry| self.read_transition_at(&entry.file, entry.byte_offset))
+            .collect()
+    }
+
+    fn append_index_entry(&self, entry: &IndexEntry) -> Result<(), RecorderError> {
+        let json = serde_json::to_string(entry)
+            .map_err(|e| RecorderError::SerializeError(e.to_string()))?;
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.index_file)
+            .map_err(|e| RecorderError::StorageError(e.to_string()))?;
+
+        writeln!(file, "{}", json).map_err(|e| RecorderError::StorageError(e.to_string()))
+    }
+
+    fn read_index(&self) -> Result<Vec<IndexEntry>, RecorderError> {
+        if !self.index_file.exists() {
+            return Ok(Vec::new());
+        }
+
+        let content = std::fs::read_to_string(&self.index_file)
+            .map_err(|e| RecorderError::StorageError(e.to_string()))?;
+
+        Ok(content
+            .lines()
+            .filter_map(|line| serde_json::from_str::<IndexEntry>(line).ok())
+            .collect())
+    }
+
+    fn read_transition_at(&self, file: &Path, byte_offset: u64) -> Result<Transition, RecorderError> {
+        use std::io::{BufRead, BufReader, Seek, SeekFrom};
+
+        let mut handle = std::fs::File::open(file)
+            .map_err(|e| RecorderError::StorageError(e.to_string()))?;
+        handle
+            .seek(SeekFrom::Start(byte_offset))
+            .map_err(|e| RecorderError::StorageError(e.to_string()))?;
+
+        let mut line = String::new();
+        BufReader::new(handle)
+            .read_line(&mut line)
+            .map_err(|e| RecorderError::StorageError(e.to_string()))?;
+
+        let mut transition: Transition = serde_json::from_str(line.trim_end())
+            .map_err(|e| RecorderError::SerializeError(e.to_string()))?;
+        self.cas.hydrate_transition(&mut transition);
+        Ok(transition)
     }
 }
 
@@ -218,7 +745,7 @@ impl TransitionRecorder {
 pub enum RecorderError {
     #[error("Storage error: {0}")]
     StorageError(String),
-    
+
     #[error("Serialization error: {0}")]
     SerializeError(String),
 }
\ No newline at end of file
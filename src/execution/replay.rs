@@ -0,0 +1,273 @@
+//! Reconstruct cumulative session state at any past transition.
+//!
+//! Folds an ordered stream of [`Transition`]s in `recorded_at` order: starting
+//! from the first `before` snapshot, each step applies `new_messages()` to
+//! accumulate the conversation and `file_changes()` to evolve the file map.
+//! This turns a flat transition log into something that can rewind to, and
+//! resume from, any past state.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+use crate::execution::{ChangeKind, ClaudePrompt, FileSnapshot, Transition};
+use crate::types::MessageRecord;
+
+/// Cumulative state as of a particular transition in a session's history.
+pub struct ReplayState<'a> {
+    pub transition: &'a Transition,
+    pub messages: Vec<&'a MessageRecord>,
+    pub files: HashMap<PathBuf, FileSnapshot>,
+}
+
+/// Rewinds a session's ordered transitions to any point in its history.
+pub struct Replay<'a> {
+    transitions: Vec<&'a Transition>,
+}
+
+impl<'a> Replay<'a> {
+    /// Build a replay over `transitions`, sorting them into `recorded_at`
+    /// order first since callers may hand them over in any order.
+    pub fn new(mut transitions: Vec<&'a Transition>) -> Self {
+        transitions.sort_by_key(|t| t.recorded_at);
+        Self { transitions }
+    }
+
+    /// Reconstruct state as of the transition with the given id.
+    pub fn at(&self, id: Uuid) -> Option<ReplayState<'a>> {
+        let index = self.transitions.iter().position(|t| t.id == id)?;
+        Some(self.fold_through(index))
+    }
+
+    /// Reconstruct state as of the last transition recorded at or before `ts`.
+    pub fn at_time(&self, ts: DateTime<Utc>) -> Option<ReplayState<'a>> {
+        let index = self.transitions.iter().rposition(|t| t.recorded_at <= ts)?;
+        Some(self.fold_through(index))
+    }
+
+    /// The most recent reconstructed state (the head of the session).
+    pub fn head(&self) -> Option<ReplayState<'a>> {
+        if self.transitions.is_empty() {
+            None
+        } else {
+            Some(self.fold_through(self.transitions.len() - 1))
+        }
+    }
+
+    /// The prompt/resume parameters needed to branch a new run from the state
+    /// just after transition `id` completed, i.e. `resume_session_id` set to
+    /// that transition's session so a fresh prompt continues from there.
+    pub fn fork(&self, id: Uuid) -> Option<ClaudePrompt> {
+        let transition = self.transitions.iter().find(|t| t.id == id)?;
+        Some(ClaudePrompt {
+            text: String::new(),
+            continue_session: false,
+            resume_session_id: Some(transition.execution.session_id.clone()),
+        })
+    }
+
+    fn fold_through(&self, last_index: usize) -> ReplayState<'a> {
+        let mut messages: Vec<&MessageRecord> = Vec::new();
+        let mut files: HashMap<PathBuf, FileSnapshot> = self.transitions[0].before.files.clone();
+
+        for transition in &self.transitions[..=last_index] {
+            messages.extend(transition.new_messages());
+            for change in transition.file_changes() {
+                if change.kind == ChangeKind::Deleted {
+                    files.remove(&change.path);
+                } else if let Some(snapshot) = transition.after.files.get(&change.path) {
+                    files.insert(change.path.clone(), snapshot.clone());
+                }
+            }
+        }
+
+        ReplayState {
+            transition: self.transitions[last_index],
+            messages,
+            files,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execution::{ClaudeExecution, EnvironmentSnapshot};
+
+    fn snapshot(files: &[(&str, &str)]) -> EnvironmentSnapshot {
+        EnvironmentSnapshot {
+            files: files
+                .iter()
+                .map(|(path, body)| {
+                    (
+                        PathBuf::from(path),
+                        FileSnapshot {
+                            hash: format!("hash:{}", body),
+                            size: body.len() as u64,
+                            body: Some(body.to_string()),
+                        },
+                    )
+                })
+                .collect(),
+            session_file: PathBuf::from("session.jsonl"),
+            session_id: None,
+            timestamp: Utc::now(),
+            session: None,
+        }
+    }
+
+    fn transition(
+        id: Uuid,
+        recorded_at: DateTime<Utc>,
+        session_id: &str,
+        before: EnvironmentSnapshot,
+        after: EnvironmentSnapshot,
+    ) -> Transition {
+        Transition {
+            id,
+            before,
+            prompt: ClaudePrompt::default(),
+            execution: ClaudeExecution {
+                prompt: ClaudePrompt::default(),
+                response: String::new(),
+                session_id: session_id.to_string(),
+                cost: 0.0,
+                duration_ms: 0,
+                model: "claude".to_string(),
+                timestamp: recorded_at,
+            },
+            after,
+            recorded_at,
+            metadata: Default::default(),
+        }
+    }
+
+    #[test]
+    fn fold_through_tracks_added_modified_and_removed_files_across_transitions() {
+        let t0 = Uuid::new_v4();
+        let t1 = Uuid::new_v4();
+        let t2 = Uuid::new_v4();
+        let base = Utc::now();
+
+        // t0: a.txt created.
+        let first = transition(
+            t0,
+            base,
+            "session-1",
+            snapshot(&[]),
+            snapshot(&[("a.txt", "hello")]),
+        );
+        // t1: a.txt modified, b.txt created.
+        let second = transition(
+            t1,
+            base + chrono::Duration::seconds(1),
+            "session-1",
+            snapshot(&[("a.txt", "hello")]),
+            snapshot(&[("a.txt", "hello world"), ("b.txt", "b")]),
+        );
+        // t2: a.txt deleted.
+        let third = transition(
+            t2,
+            base + chrono::Duration::seconds(2),
+            "session-1",
+            snapshot(&[("a.txt", "hello world"), ("b.txt", "b")]),
+            snapshot(&[("b.txt", "b")]),
+        );
+
+        let replay = Replay::new(vec![&first, &second, &third]);
+
+        let after_first = replay.at(t0).unwrap();
+        assert_eq!(after_first.files.len(), 1);
+        assert_eq!(
+            after_first.files.get(&PathBuf::from("a.txt")).unwrap().body,
+            Some("hello".to_string())
+        );
+
+        let after_second = replay.at(t1).unwrap();
+        assert_eq!(after_second.files.len(), 2);
+        assert_eq!(
+            after_second.files.get(&PathBuf::from("a.txt")).unwrap().body,
+            Some("hello world".to_string())
+        );
+
+        let after_third = replay.at(t2).unwrap();
+        assert_eq!(after_third.files.len(), 1);
+        assert!(!after_third.files.contains_key(&PathBuf::from("a.txt")));
+        assert!(after_third.files.contains_key(&PathBuf::from("b.txt")));
+    }
+
+    #[test]
+    fn at_time_reconstructs_the_last_transition_at_or_before_the_given_instant() {
+        let t0 = Uuid::new_v4();
+        let t1 = Uuid::new_v4();
+        let base = Utc::now();
+        let first = transition(
+            t0,
+            base,
+            "session-1",
+            snapshot(&[]),
+            snapshot(&[("a.txt", "1")]),
+        );
+        let second = transition(
+            t1,
+            base + chrono::Duration::seconds(10),
+            "session-1",
+            snapshot(&[("a.txt", "1")]),
+            snapshot(&[("a.txt", "2")]),
+        );
+        let replay = Replay::new(vec![&first, &second]);
+
+        let mid = replay.at_time(base + chrono::Duration::seconds(5)).unwrap();
+        assert_eq!(mid.transition.id, t0);
+
+        let before_anything = replay.at_time(base - chrono::Duration::seconds(1));
+        assert!(before_anything.is_none());
+    }
+
+    #[test]
+    fn head_returns_the_most_recent_transition() {
+        let t0 = Uuid::new_v4();
+        let t1 = Uuid::new_v4();
+        let base = Utc::now();
+        let first = transition(t0, base, "session-1", snapshot(&[]), snapshot(&[]));
+        let second = transition(
+            t1,
+            base + chrono::Duration::seconds(1),
+            "session-1",
+            snapshot(&[]),
+            snapshot(&[]),
+        );
+
+        // Hand transitions in reverse order to confirm Replay::new sorts them.
+        let replay = Replay::new(vec![&second, &first]);
+        assert_eq!(replay.head().unwrap().transition.id, t1);
+    }
+
+    #[test]
+    fn head_is_none_for_an_empty_replay() {
+        let replay = Replay::new(vec![]);
+        assert!(replay.head().is_none());
+    }
+
+    #[test]
+    fn fork_resumes_from_the_target_transitions_own_session_id() {
+        let t0 = Uuid::new_v4();
+        let base = Utc::now();
+        let first = transition(t0, base, "session-abc", snapshot(&[]), snapshot(&[]));
+        let replay = Replay::new(vec![&first]);
+
+        let prompt = replay.fork(t0).unwrap();
+        assert_eq!(prompt.resume_session_id, Some("session-abc".to_string()));
+        assert!(!prompt.continue_session);
+    }
+
+    #[test]
+    fn fork_returns_none_for_an_unknown_transition_id() {
+        let t0 = Uuid::new_v4();
+        let base = Utc::now();
+        let first = transition(t0, base, "session-abc", snapshot(&[]), snapshot(&[]));
+        let replay = Replay::new(vec![&first]);
+
+        assert!(replay.fork(Uuid::new_v4()).is_none());
+    }
+}
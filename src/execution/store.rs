@@ -0,0 +1,249 @@
+//! Named, persistable store of conversations, modeled on aichat's sessions
+//! directory: conversations live as named JSON files under a directory
+//! (defaulting to `~/.claude_sdk/conversations/`) so callers can list, resume,
+//! rename, and delete them by name instead of juggling explicit paths.
+//!
+//! [`super::Conversation::save_named`]/[`super::Conversation::load_named`] are
+//! thin convenience wrappers over this store for callers who'd rather not
+//! hold a `ConversationStore` handle directly.
+
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::execution::{blob_store::CasStore, Conversation, ConversationError, RecordingPolicy, Workspace};
+
+/// Summary metadata about a stored conversation, returned by
+/// [`ConversationStore::list`].
+#[derive(Debug, Clone)]
+pub struct ConversationEntry {
+    pub name: String,
+    pub id: Uuid,
+    pub transition_count: usize,
+    pub total_cost_usd: f64,
+    pub created_at: DateTime<Utc>,
+    pub last_model: Option<String>,
+    pub last_modified: DateTime<Utc>,
+}
+
+/// Mirrors just enough of `Conversation`'s on-disk shape to summarize a
+/// stored conversation without fully deserializing every transition.
+#[derive(Debug, Deserialize)]
+struct StoredSummary {
+    id: Uuid,
+    transitions: Vec<serde_json::Value>,
+    metadata: StoredMetadataSummary,
+}
+
+#[derive(Debug, Deserialize)]
+struct StoredMetadataSummary {
+    total_cost_usd: f64,
+    created_at: DateTime<Utc>,
+    #[serde(default)]
+    last_model: Option<String>,
+}
+
+/// Directory-backed store of named conversations.
+pub struct ConversationStore {
+    dir: PathBuf,
+}
+
+impl ConversationStore {
+    /// Open (creating if necessary) the store directory. Defaults to
+    /// `~/.claude_sdk/conversations` when `dir` is `None`.
+    pub fn new(dir: Option<PathBuf>) -> Result<Self, ConversationError> {
+        let dir = dir.unwrap_or_else(Self::default_dir);
+        fs::create_dir_all(&dir)?;
+        Ok(Self { dir })
+    }
+
+    fn default_dir() -> PathBuf {
+        home::home_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join(".claude_sdk")
+            .join("conversations")
+    }
+
+    /// Resolve `name` to a path under `self.dir`, rejecting anything that
+    /// could escape it. `name` is arbitrary caller-supplied (potentially
+    /// LLM-originated) text, not a trusted path component, so it's checked
+    /// against a plain-component allow-list rather than joined directly.
+    fn path_for(&self, name: &str) -> Result<PathBuf, ConversationError> {
+        let is_plain_component = !name.is_empty()
+            && name != "."
+            && name != ".."
+            && !name.contains('/')
+            && !name.contains('\\');
+        if !is_plain_component {
+            return Err(ConversationError::InvalidName(name.to_string()));
+        }
+        Ok(self.dir.join(format!("{}.json", name)))
+    }
+
+    /// Create and persist a brand-new named conversation in `workspace`.
+    pub fn create(
+        &self,
+        name: &str,
+        workspace: Arc<Workspace>,
+    ) -> Result<Conversation, ConversationError> {
+        let conversation = Conversation::new(workspace);
+        conversation.save(&self.path_for(name)?)?;
+        Ok(conversation)
+    }
+
+    /// List every stored conversation's name and summary metadata, sorted by
+    /// name.
+    pub fn list(&self) -> Result<Vec<ConversationEntry>, ConversationError> {
+        let mut entries = Vec::new();
+
+        for dir_entry in fs::read_dir(&self.dir)? {
+            let path = dir_entry?.path();
+            if path.extension().map(|ext| ext != "json").unwrap_or(true) {
+                continue;
+            }
+            let Some(name) = path.file_stem().and_then(|s| s.to_str()) else {
+                continue;
+            };
+            let Ok(data) = fs::read_to_string(&path) else {
+                continue;
+            };
+            let Ok(saved) = serde_json::from_str::<StoredSummary>(&data) else {
+                continue;
+            };
+            let last_modified = fs::metadata(&path)
+                .and_then(|m| m.modified())
+                .map(DateTime::<Utc>::from)
+                .unwrap_or_else(|_| Utc::now());
+
+            entries.push(ConversationEntry {
+                name: name.to_string(),
+                id: saved.id,
+                transition_count: saved.transitions.len(),
+                total_cost_usd: saved.metadata.total_cost_usd,
+                created_at: saved.metadata.created_at,
+                last_model: saved.metadata.last_model,
+                last_modified,
+            });
+        }
+
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(entries)
+    }
+
+    /// Names only, suitable for shell/REPL `.conversation` tab-completion.
+    pub fn names(&self) -> Result<Vec<String>, ConversationError> {
+        Ok(self.list()?.into_iter().map(|entry| entry.name).collect())
+    }
+
+    /// Load the conversation stored under `name`, or start a brand-new one
+    /// against `workspace` if none exists yet. The common case for a
+    /// resume-by-name flow, where the caller doesn't care whether this is
+    /// the first turn with this name or the hundredth.
+    pub fn open(
+        &self,
+        name: &str,
+        workspace: Arc<Workspace>,
+        recording_policy: RecordingPolicy,
+    ) -> Result<Conversation, ConversationError> {
+        let path = self.path_for(name)?;
+        if path.exists() {
+            self.load(name, workspace, recording_policy)
+        } else {
+            let conversation = Conversation::new_with_options(workspace, recording_policy)?;
+            conversation.save(&path)?;
+            Ok(conversation)
+        }
+    }
+
+    /// Load a named conversation back into a live `Conversation` against
+    /// `workspace`.
+    pub fn load(
+        &self,
+        name: &str,
+        workspace: Arc<Workspace>,
+        recording_policy: RecordingPolicy,
+    ) -> Result<Conversation, ConversationError> {
+        Conversation::load(&self.path_for(name)?, workspace, recording_policy)
+    }
+
+    /// Persist `conversation` under `name`.
+    pub fn save(&self, name: &str, conversation: &Conversation) -> Result<(), ConversationError> {
+        conversation.save(&self.path_for(name)?)
+    }
+
+    /// Delete every blob in `workspace`'s CAS directory that isn't
+    /// referenced by any conversation currently stored here, returning how
+    /// many were removed. Conversations against the same workspace share one
+    /// CAS, so this scans every stored conversation's references before
+    /// deciding what's safe to delete, not just one.
+    pub fn gc_blobs(&self, workspace: &Workspace) -> Result<usize, ConversationError> {
+        let mut referenced = std::collections::HashSet::new();
+
+        for dir_entry in fs::read_dir(&self.dir)? {
+            let path = dir_entry?.path();
+            if path.extension().map(|ext| ext != "json").unwrap_or(true) {
+                continue;
+            }
+            if let Ok(hashes) = Conversation::referenced_blob_hashes(&path) {
+                referenced.extend(hashes);
+            }
+        }
+
+        Ok(CasStore::new(workspace.path()).gc(&referenced)?)
+    }
+
+    /// Rename a stored conversation.
+    pub fn rename(&self, from: &str, to: &str) -> Result<(), ConversationError> {
+        fs::rename(self.path_for(from)?, self.path_for(to)?)?;
+        Ok(())
+    }
+
+    /// Delete a stored conversation.
+    pub fn delete(&self, name: &str) -> Result<(), ConversationError> {
+        fs::remove_file(self.path_for(name)?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store() -> ConversationStore {
+        ConversationStore {
+            dir: PathBuf::from("/tmp/claude_sdk_test_conversations"),
+        }
+    }
+
+    #[test]
+    fn plain_name_resolves_under_the_store_dir() {
+        let store = store();
+        let path = store.path_for("my-session").unwrap();
+        assert_eq!(path, store.dir.join("my-session.json"));
+    }
+
+    #[test]
+    fn parent_dir_traversal_is_rejected() {
+        let store = store();
+        assert!(store.path_for("../../etc/cron.d/evil").is_err());
+        assert!(store.path_for("..").is_err());
+        assert!(store.path_for("foo/../../bar").is_err());
+    }
+
+    #[test]
+    fn embedded_path_separators_are_rejected() {
+        let store = store();
+        assert!(store.path_for("foo/bar").is_err());
+        assert!(store.path_for("foo\\bar").is_err());
+        assert!(store.path_for("/etc/passwd").is_err());
+    }
+
+    #[test]
+    fn empty_name_is_rejected() {
+        let store = store();
+        assert!(store.path_for("").is_err());
+    }
+}
@@ -0,0 +1,128 @@
+//! Session-wide matching of `ToolUse`/`ToolResult` blocks across transition
+//! boundaries.
+//!
+//! `Transition::tool_executions()` only pairs calls and results within a
+//! single turn's `new_messages()`. Real sessions often issue a tool call in
+//! one turn and receive its result in a later one (e.g. sub-agent or
+//! long-running tools), so a whole-session view is needed to avoid silently
+//! dropping those as never-resolved.
+
+use std::collections::HashMap;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::execution::Transition;
+use crate::types::{ContentBlock, ToolExecution, ToolResult as TypesToolResult};
+
+/// A tool call that has been issued but has not (yet) observed a matching
+/// `ToolResult` anywhere in the scanned span.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingToolCall {
+    pub tool_use_id: String,
+    pub tool_name: String,
+    pub input: serde_json::Value,
+    pub started_at: DateTime<Utc>,
+}
+
+/// Either a fully resolved tool call or one still awaiting its result.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "status")]
+pub enum ToolCallStatus {
+    Completed(ToolExecution),
+    Unresolved(PendingToolCall),
+}
+
+/// Threads every `tool_use_id` across an ordered slice of [`Transition`]s (or
+/// a whole `ParsedSession`'s worth of them), pairing each `ToolUse` with its
+/// eventual `ToolResult` regardless of which turn it lands in.
+pub struct ToolCallGraph {
+    calls: Vec<ToolCallStatus>,
+}
+
+impl ToolCallGraph {
+    /// Build the graph by scanning transitions in order, carrying unresolved
+    /// tool uses forward across transition boundaries instead of dropping
+    /// them at the end of each turn.
+    pub fn from_transitions(transitions: &[Transition]) -> Self {
+        let mut pending: HashMap<String, (String, serde_json::Value, DateTime<Utc>)> =
+            HashMap::new();
+        let mut calls = Vec::new();
+
+        for transition in transitions {
+            for message in transition.new_messages() {
+                for content in &message.message.content {
+                    match content {
+                        ContentBlock::ToolUse { id, name, input } => {
+                            pending.insert(id.clone(), (name.clone(), input.clone(), message.timestamp));
+                        }
+                        ContentBlock::ToolResult { tool_use_id, content, is_error } => {
+                            if let Some((tool_name, input, start_time)) = pending.remove(tool_use_id) {
+                                let duration = message
+                                    .timestamp
+                                    .signed_duration_since(start_time)
+                                    .to_std()
+                                    .unwrap_or(std::time::Duration::from_secs(0));
+
+                                let tool_result = TypesToolResult {
+                                    tool_use_id: tool_use_id.clone(),
+                                    content: content.as_ref().map(|c| c.as_text()).unwrap_or_default(),
+                                    stdout: None,
+                                    stderr: None,
+                                    interrupted: false,
+                                    is_error: is_error.unwrap_or(false),
+                                    metadata: serde_json::Value::Null,
+                                };
+
+                                calls.push(ToolCallStatus::Completed(ToolExecution::new(
+                                    tool_name, input, tool_result, duration, start_time,
+                                )));
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        // Anything left in `pending` never saw a result within this span.
+        let mut still_pending: Vec<_> = pending.into_iter().collect();
+        still_pending.sort_by_key(|(_, (_, _, started_at))| *started_at);
+        for (tool_use_id, (tool_name, input, started_at)) in still_pending {
+            calls.push(ToolCallStatus::Unresolved(PendingToolCall {
+                tool_use_id,
+                tool_name,
+                input,
+                started_at,
+            }));
+        }
+
+        Self { calls }
+    }
+
+    /// All tool calls, resolved and unresolved.
+    pub fn calls(&self) -> &[ToolCallStatus] {
+        &self.calls
+    }
+
+    /// Only the tool calls that completed with a result.
+    pub fn completed(&self) -> Vec<&ToolExecution> {
+        self.calls
+            .iter()
+            .filter_map(|c| match c {
+                ToolCallStatus::Completed(exec) => Some(exec),
+                ToolCallStatus::Unresolved(_) => None,
+            })
+            .collect()
+    }
+
+    /// Tool calls still awaiting a result anywhere in the scanned span,
+    /// including nested/sub-agent invocations whose result never arrived.
+    pub fn pending(&self) -> Vec<&PendingToolCall> {
+        self.calls
+            .iter()
+            .filter_map(|c| match c {
+                ToolCallStatus::Unresolved(p) => Some(p),
+                ToolCallStatus::Completed(_) => None,
+            })
+            .collect()
+    }
+}
@@ -6,9 +6,11 @@
 
 use std::path::PathBuf;
 use std::collections::HashMap;
+use std::sync::Arc;
 use crate::execution::{
-    ClaudeExecutor, ExecutorError,
-    EnvironmentObserver, EnvironmentSnapshot, ObserverError,
+    Capability, ClaudeExecutor, Decision, ExecutorError,
+    EnvironmentObserver, EnvironmentSnapshot, FileSnapshot, ObserverError, PermissionDecision,
+    SessionLocator, ToolRequest, WatchHandle, WorkspaceChangeEvent,
 };
 
 /// Workspace provides the infrastructure for executing Claude commands
@@ -41,7 +43,26 @@ impl Workspace {
     pub fn path(&self) -> &PathBuf {
         &self.workspace_path
     }
-    
+
+    /// Point session-file discovery at `dir` instead of
+    /// `~/.claude/projects`, for sandboxed hosts and deterministic tests.
+    pub fn with_session_dir(mut self, dir: PathBuf) -> Self {
+        self.observer = self.observer.with_session_dir(dir);
+        self
+    }
+
+    /// Replace session-file discovery with an arbitrary [`SessionLocator`].
+    pub fn with_session_locator(mut self, locator: Arc<dyn SessionLocator>) -> Self {
+        self.observer.set_locator(locator);
+        self
+    }
+
+    /// Like [`Workspace::with_session_dir`], but for a workspace that's
+    /// already been constructed (e.g. behind an `Arc<Mutex<_>>`).
+    pub fn set_session_dir(&mut self, dir: PathBuf) {
+        self.observer.set_locator(Arc::new(crate::execution::DirSessionLocator::new(dir)));
+    }
+
     /// Take a snapshot of the current workspace state
     pub fn snapshot(&self) -> Result<EnvironmentSnapshot, WorkspaceError> {
         self.observer.snapshot()
@@ -49,7 +70,7 @@ impl Workspace {
     }
 
     /// Capture only the workspace files without session data
-    pub fn snapshot_files(&self) -> Result<HashMap<PathBuf, String>, WorkspaceError> {
+    pub fn snapshot_files(&self) -> Result<HashMap<PathBuf, FileSnapshot>, WorkspaceError> {
         self.observer.snapshot_files()
             .map_err(WorkspaceError::ObserverError)
     }
@@ -59,17 +80,71 @@ impl Workspace {
         self.observer.snapshot_with_session(session_id)
             .map_err(WorkspaceError::ObserverError)
     }
-    
-    /// Configure tool permissions for the executor
+
+    /// Watch the workspace filesystem, invoking `callback` with a fresh
+    /// snapshot whenever tracked files settle after a burst of changes.
+    pub fn watch<F>(&self, callback: F) -> Result<WatchHandle, WorkspaceError>
+    where
+        F: FnMut(EnvironmentSnapshot) + Send + 'static,
+    {
+        self.observer.watch(callback).map_err(WorkspaceError::ObserverError)
+    }
+
+    /// Like [`Workspace::watch`], but each event also carries the files
+    /// that changed since the previous tick. The session transcript is not
+    /// watched, so each event's `tool_executions` is always empty.
+    pub fn watch_changes<F>(&self, callback: F) -> Result<WatchHandle, WorkspaceError>
+    where
+        F: FnMut(WorkspaceChangeEvent) + Send + 'static,
+    {
+        self.observer.watch_changes(callback).map_err(WorkspaceError::ObserverError)
+    }
+
+    /// Like [`Workspace::watch`], but delivers snapshots over a channel.
+    pub fn watch_channel(
+        &self,
+    ) -> Result<(WatchHandle, std::sync::mpsc::Receiver<EnvironmentSnapshot>), WorkspaceError> {
+        self.observer.watch_channel().map_err(WorkspaceError::ObserverError)
+    }
+
+    /// Configure tool permissions for the executor. Internally replaces the
+    /// permission profile with a single-permission [`Capability`] built from
+    /// `tools` plus the deny scopes of the profile installed before.
     pub fn set_allowed_tools(&mut self, tools: Option<String>) {
         self.executor.set_allowed_tools(tools);
     }
-    
+
     /// Configure disallowed tools for the executor
     pub fn set_disallowed_tools(&mut self, tools: Option<String>) {
         self.executor.set_disallowed_tools(tools);
     }
-    
+
+    /// Attach a structured permission profile, replacing whatever tool
+    /// lists or profile were set before. Compiled to
+    /// `--allowedTools`/`--disallowedTools` at execution time.
+    pub fn set_permission_profile(&mut self, profile: Capability) {
+        self.executor.set_permission_profile(profile);
+    }
+
+    /// Register an interactive permission handler, consulted by
+    /// `send_streaming` turns whenever the model attempts a tool the static
+    /// profile doesn't explicitly cover. See
+    /// [`ClaudeExecutor::set_permission_handler`] for what
+    /// `AllowAndRemember`/`DenyAndRemember` do and their limits.
+    pub fn set_permission_handler(
+        &mut self,
+        handler: impl FnMut(&ToolRequest) -> PermissionDecision + Send + 'static,
+    ) {
+        self.executor.set_permission_handler(handler);
+    }
+
+    /// Evaluate whether `tool` (with raw argument string `args`, e.g. a
+    /// shell command or file path) would be allowed under the current
+    /// permission profile, without running Claude.
+    pub fn check(&self, tool: &str, args: &str) -> Decision {
+        self.executor.permission_profile().check(tool, args)
+    }
+
     /// Enable dangerous mode that skips all permission checks
     /// This should only be used in tests or when explicitly requested
     pub fn set_skip_permissions(&mut self, skip: bool) {
@@ -81,6 +156,25 @@ impl Workspace {
     pub fn set_model(&mut self, model: Option<String>) {
         self.executor.set_model(model);
     }
+
+    /// Register an external tool server (MCP-style): spawn `command` with
+    /// `args`/`env` and enumerate the tools it exposes over a JSON-RPC
+    /// handshake. The server is kept registered so its config is passed to
+    /// every subsequent Claude invocation via `--mcp-config`, and its
+    /// subprocess is terminated when this workspace is dropped. Returns the
+    /// tool names it advertised, for wiring into a [`Capability`] via
+    /// `set_permission_profile`.
+    pub fn add_tool_server(
+        &mut self,
+        name: impl Into<String>,
+        command: impl Into<String>,
+        args: Vec<String>,
+        env: HashMap<String, String>,
+    ) -> Result<Vec<String>, WorkspaceError> {
+        self.executor
+            .add_tool_server(name, command, args, env)
+            .map_err(WorkspaceError::ExecutorError)
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -1,6 +1,115 @@
 use pyo3::prelude::*;
 use crate::types::{ContentBlock, MessageRecord as RustMessageRecord, ParsedSession as RustParsedSession, TokenUsage};
-use std::collections::HashMap;
+use crate::python::utils::{datetime_to_py, json_to_py};
+use chrono::{DateTime, Datelike, Duration as ChronoDuration, LocalResult, NaiveDate, NaiveDateTime, TimeZone, Timelike, Utc};
+use chrono_tz::Tz;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Parse a Python `datetime` object or an ISO-8601 string into a real
+/// `DateTime<Utc>` instant, instead of comparing ISO strings lexicographically
+/// (which breaks across offsets and fractional-second formatting).
+fn parse_datetime_arg(obj: &Bound<'_, PyAny>) -> PyResult<DateTime<Utc>> {
+    if let Ok(s) = obj.extract::<String>() {
+        return DateTime::parse_from_rfc3339(&s)
+            .map(|dt| dt.with_timezone(&Utc))
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(format!("invalid ISO-8601 timestamp: {e}")));
+    }
+
+    // A `datetime` object, naive or timezone-aware: `.timestamp()` already
+    // accounts for `tzinfo` when present, and falls back to the interpreter's
+    // local timezone for naive datetimes, matching Python's own semantics.
+    let epoch_secs: f64 = obj.call_method0("timestamp")?.extract()?;
+    DateTime::from_timestamp(epoch_secs.floor() as i64, (epoch_secs.fract() * 1e9) as u32)
+        .ok_or_else(|| pyo3::exceptions::PyValueError::new_err("timestamp out of range"))
+}
+
+/// Parse a Python `datetime` object or an ISO-8601 string the same way as
+/// [`parse_datetime_arg`], except when `tz` is given: the value's own
+/// wall-clock fields (year/month/day/hour/minute/second, or the matching
+/// components of an ISO string with no offset) are then localized in `tz`
+/// instead of being read via `tzinfo`/the interpreter's local timezone.
+/// This lets a caller pass local wall-clock bounds (e.g. "9am in
+/// America/New_York") and have them converted to the same `DateTime<Utc>`
+/// instant session timestamps are compared against.
+fn parse_datetime_arg_in_tz(obj: &Bound<'_, PyAny>, tz: Option<Tz>) -> PyResult<DateTime<Utc>> {
+    let Some(tz) = tz else {
+        return parse_datetime_arg(obj);
+    };
+
+    let naive = if let Ok(s) = obj.extract::<String>() {
+        DateTime::parse_from_rfc3339(&s)
+            .map(|dt| dt.naive_local())
+            .or_else(|_| NaiveDateTime::parse_from_str(&s, "%Y-%m-%dT%H:%M:%S"))
+            .or_else(|_| NaiveDateTime::parse_from_str(&s, "%Y-%m-%d %H:%M:%S"))
+            .or_else(|_| {
+                NaiveDate::parse_from_str(&s, "%Y-%m-%d")
+                    .map(|d| d.and_hms_opt(0, 0, 0).expect("midnight is always valid"))
+            })
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(format!("invalid local timestamp '{s}': {e}")))?
+    } else {
+        let year: i32 = obj.getattr("year")?.extract()?;
+        let month: u32 = obj.getattr("month")?.extract()?;
+        let day: u32 = obj.getattr("day")?.extract()?;
+        let hour: u32 = obj.getattr("hour")?.extract()?;
+        let minute: u32 = obj.getattr("minute")?.extract()?;
+        let second: u32 = obj.getattr("second")?.extract()?;
+        NaiveDate::from_ymd_opt(year, month, day)
+            .and_then(|d| d.and_hms_opt(hour, minute, second))
+            .ok_or_else(|| pyo3::exceptions::PyValueError::new_err("invalid datetime components"))?
+    };
+
+    match tz.from_local_datetime(&naive) {
+        LocalResult::Single(dt) | LocalResult::Ambiguous(dt, _) => Ok(dt.with_timezone(&Utc)),
+        LocalResult::None => Err(pyo3::exceptions::PyValueError::new_err(
+            "local time falls in a DST gap",
+        )),
+    }
+}
+
+/// Truncate `dt` down to the start of its `granularity` bucket ("day",
+/// "week", or "month"), in `dt`'s own timezone. Weeks start on Monday
+/// (ISO), months on the 1st. A DST gap landing exactly on a bucket
+/// boundary falls back to the UTC-offset interpretation rather than
+/// panicking.
+fn truncate_to_bucket<Tz2: TimeZone>(dt: DateTime<Tz2>, granularity: &str) -> DateTime<Tz2> {
+    let tz = dt.timezone();
+    let naive_date = match granularity {
+        "week" => dt.naive_local().date() - ChronoDuration::days(dt.weekday().num_days_from_monday() as i64),
+        "month" => NaiveDate::from_ymd_opt(dt.year(), dt.month(), 1).expect("valid year/month"),
+        _ => dt.naive_local().date(),
+    };
+    let naive_midnight = naive_date.and_hms_opt(0, 0, 0).expect("midnight is always valid");
+    match tz.from_local_datetime(&naive_midnight) {
+        LocalResult::Single(d) | LocalResult::Ambiguous(d, _) => d,
+        LocalResult::None => tz.from_utc_datetime(&naive_midnight),
+    }
+}
+
+/// Advance `dt` to the start of the next `granularity` bucket after the one
+/// it's currently in, handling month-length and year-rollover. `dt` need
+/// not already be truncated.
+fn next_bucket<Tz2: TimeZone>(dt: DateTime<Tz2>, granularity: &str) -> DateTime<Tz2> {
+    let tz = dt.timezone();
+    let naive_date = dt.naive_local().date();
+    let next_date = match granularity {
+        "week" => naive_date + ChronoDuration::days(7),
+        "month" => {
+            if naive_date.month() == 12 {
+                NaiveDate::from_ymd_opt(naive_date.year() + 1, 1, 1).expect("valid year/month")
+            } else {
+                NaiveDate::from_ymd_opt(naive_date.year(), naive_date.month() + 1, 1).expect("valid year/month")
+            }
+        }
+        _ => naive_date + ChronoDuration::days(1),
+    };
+    let naive_midnight = next_date.and_hms_opt(0, 0, 0).expect("midnight is always valid");
+    match tz.from_local_datetime(&naive_midnight) {
+        LocalResult::Single(d) | LocalResult::Ambiguous(d, _) => d,
+        LocalResult::None => tz.from_utc_datetime(&naive_midnight),
+    }
+}
 
 /// Individual message in a Claude Code conversation.
 /// 
@@ -125,16 +234,53 @@ impl Message {
     }
     
     /// Check if this message contains tool usage.
-    /// 
+    ///
     /// Returns:
     ///     bool: True if message contains any tool use blocks
-    /// 
+    ///
     /// Example:
     ///     >>> if msg.has_tool_use():
     ///     ...     print(f"Used tools: {', '.join(msg.tools)}")
     fn has_tool_use(&self) -> bool {
         self.content_blocks.iter().any(|block| matches!(block, ContentBlock::ToolUse { .. }))
     }
+
+    /// Get all tool result blocks in this message.
+    ///
+    /// Returns:
+    ///     List[ToolResultBlock]: List of tool result blocks
+    fn get_tool_results(&self) -> Vec<ToolResultBlock> {
+        let mut blocks = Vec::new();
+        for content in &self.content_blocks {
+            if let ContentBlock::ToolResult { tool_use_id, content, is_error } = content {
+                blocks.push(ToolResultBlock {
+                    tool_use_id: tool_use_id.clone(),
+                    content: content.as_ref().map(|c| c.as_text()).unwrap_or_default(),
+                    is_error: is_error.unwrap_or(false),
+                });
+            }
+        }
+        blocks
+    }
+}
+
+/// A `ContentBlock::ToolResult`, as returned by `Message.get_tool_results()`.
+#[pyclass(name = "ToolResultBlock", module = "claude_sdk")]
+#[derive(Clone)]
+pub struct ToolResultBlock {
+    #[pyo3(get)]
+    pub tool_use_id: String,
+    #[pyo3(get)]
+    pub content: String,
+    #[pyo3(get)]
+    pub is_error: bool,
+}
+
+#[pymethods]
+impl ToolResultBlock {
+    fn __repr__(&self) -> String {
+        format!("<ToolResultBlock tool_use_id='{}' is_error={}>", self.tool_use_id, self.is_error)
+    }
 }
 
 impl Message {
@@ -216,8 +362,145 @@ impl MessageIterator {
     }
 }
 
+/// One root-to-leaf path through the conversation tree, as returned by
+/// `Session.get_all_threads()`, with the branch points it passed through so
+/// callers can reconstruct the true tree shape instead of flat paths.
+#[pyclass(name = "Thread", module = "claude_sdk")]
+#[derive(Clone)]
+pub struct Thread {
+    #[pyo3(get)]
+    pub messages: Vec<Message>,
+    #[pyo3(get)]
+    pub root_uuid: String,
+    #[pyo3(get)]
+    pub leaf_uuid: String,
+    #[pyo3(get)]
+    pub depth: usize,
+    #[pyo3(get)]
+    pub branch_point_uuids: Vec<String>,
+}
+
+#[pymethods]
+impl Thread {
+    fn __len__(&self) -> usize {
+        self.messages.len()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("<Thread root='{}' leaf='{}' depth={}>", self.root_uuid, self.leaf_uuid, self.depth)
+    }
+}
+
+/// Attribute each assistant turn's actual token cost to the tools it
+/// invoked, splitting evenly across `ToolUse` blocks when a turn made
+/// several parallel calls, instead of spreading the session total evenly by
+/// tool usage count.
+fn cost_by_tool(messages: &[Message]) -> Vec<(String, f64)> {
+    let mut totals: HashMap<String, f64> = HashMap::new();
+    for msg in messages {
+        if msg.tools.is_empty() {
+            continue;
+        }
+        let share = msg.cost.unwrap_or(0.0) / msg.tools.len() as f64;
+        for tool in &msg.tools {
+            *totals.entry(tool.clone()).or_insert(0.0) += share;
+        }
+    }
+    totals.into_iter().collect()
+}
+
+/// Count sidechain messages, splitting the work across `workers` OS threads
+/// via a chunked parallel fold once `messages` is large enough to be worth
+/// it. Called inside `Session.calculate_metrics()` while the GIL is released.
+fn parallel_sidechain_count(messages: &[Message], workers: usize) -> usize {
+    const PARALLEL_THRESHOLD: usize = 2000;
+    if messages.len() < PARALLEL_THRESHOLD || workers <= 1 {
+        return messages.iter().filter(|m| m.is_sidechain).count();
+    }
+
+    let chunk_size = messages.len().div_ceil(workers).max(1);
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = messages.chunks(chunk_size)
+            .map(|chunk| scope.spawn(move || chunk.iter().filter(|m| m.is_sidechain).count()))
+            .collect();
+        handles.into_iter().map(|h| h.join().expect("worker thread panicked")).sum()
+    })
+}
+
+/// Sort key for a message under a given `sort_field` ("date" or "text").
+fn thread_sort_key(msg: &Message, field: &str) -> String {
+    match field {
+        "text" => msg.text.clone(),
+        _ => msg.timestamp.clone(),
+    }
+}
+
+fn sort_uuids_by_field(uuids: &mut [String], index: &HashMap<String, &Message>, field: &str, order: &str) {
+    uuids.sort_by(|a, b| {
+        let ka = index.get(a).map(|m| thread_sort_key(m, field)).unwrap_or_default();
+        let kb = index.get(b).map(|m| thread_sort_key(m, field)).unwrap_or_default();
+        ka.cmp(&kb)
+    });
+    if order == "desc" {
+        uuids.reverse();
+    }
+}
+
+fn sort_threads_by_field(threads: &mut [Thread], field: &str, order: &str) {
+    threads.sort_by(|a, b| {
+        let ka = a.messages.first().map(|m| thread_sort_key(m, field)).unwrap_or_default();
+        let kb = b.messages.first().map(|m| thread_sort_key(m, field)).unwrap_or_default();
+        ka.cmp(&kb)
+    });
+    if order == "desc" {
+        threads.reverse();
+    }
+}
+
+/// DFS from `uuid`, emitting one `Thread` per leaf reached and recording the
+/// uuids of branch points (nodes with more than one child) passed along the
+/// way. `path` and `branch_points` are backtracked in place.
+fn collect_threads(
+    uuid: &str,
+    path: &mut Vec<String>,
+    branch_points: &mut Vec<String>,
+    children: &HashMap<String, Vec<String>>,
+    uuid_to_msg: &HashMap<String, &Message>,
+    out: &mut Vec<Thread>,
+) {
+    let kids = children.get(uuid).cloned().unwrap_or_default();
+
+    if kids.is_empty() {
+        let messages: Vec<Message> = path.iter()
+            .filter_map(|u| uuid_to_msg.get(u))
+            .map(|m| (*m).clone())
+            .collect();
+        out.push(Thread {
+            root_uuid: path[0].clone(),
+            leaf_uuid: uuid.to_string(),
+            depth: path.len().saturating_sub(1),
+            branch_point_uuids: branch_points.clone(),
+            messages,
+        });
+        return;
+    }
+
+    let is_branch_point = kids.len() > 1;
+    if is_branch_point {
+        branch_points.push(uuid.to_string());
+    }
+    for child in &kids {
+        path.push(child.clone());
+        collect_threads(child, path, branch_points, children, uuid_to_msg, out);
+        path.pop();
+    }
+    if is_branch_point {
+        branch_points.pop();
+    }
+}
+
 /// Primary container for Claude Code session data.
-/// 
+///
 /// This class represents a complete Claude Code session, containing messages,
 /// conversation threading, tool usage information, and metadata.
 /// 
@@ -339,14 +622,8 @@ impl Session {
     #[getter]
     fn tool_costs(&self, py: Python<'_>) -> PyResult<PyObject> {
         let dict = pyo3::types::PyDict::new_bound(py);
-        for (tool, count) in &self.inner.metadata.tool_usage_count {
-            // Simple cost distribution based on usage count
-            let tool_cost = if self.inner.metadata.total_tool_calls > 0 {
-                self.total_cost * (*count as f64) / (self.inner.metadata.total_tool_calls as f64)
-            } else {
-                0.0
-            };
-            dict.set_item(tool, tool_cost)?;
+        for (tool, cost) in cost_by_tool(&self.messages) {
+            dict.set_item(tool, cost)?;
         }
         Ok(dict.into())
     }
@@ -458,7 +735,65 @@ impl Session {
         }
         Ok(filtered)
     }
-    
+
+    /// Like `filter_messages`, but evaluates `predicate` across a bounded
+    /// pool of worker threads instead of one message at a time, for sessions
+    /// with tens of thousands of messages. Each worker reacquires the GIL
+    /// only for its own batch of `call1` invocations.
+    ///
+    /// Args:
+    ///     predicate: A callable that takes a Message and returns bool
+    ///     max_workers: Worker count, defaults to the CPU count
+    ///
+    /// Returns:
+    ///     List[Message]: Messages that match the predicate, in original order
+    #[pyo3(signature = (predicate, max_workers=None))]
+    fn filter_messages_parallel(
+        &self,
+        py: Python<'_>,
+        predicate: &Bound<'_, PyAny>,
+        max_workers: Option<usize>,
+    ) -> PyResult<Vec<Message>> {
+        let workers = max_workers
+            .unwrap_or_else(|| std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1))
+            .max(1);
+
+        let chunk_size = self.messages.len().div_ceil(workers).max(1);
+        let batches: Vec<(Vec<Message>, Py<PyAny>)> = self.messages
+            .chunks(chunk_size)
+            .map(|chunk| (chunk.to_vec(), predicate.clone().unbind()))
+            .collect();
+
+        let results: Vec<PyResult<Vec<Message>>> = py.allow_threads(move || {
+            std::thread::scope(|scope| {
+                let handles: Vec<_> = batches.into_iter()
+                    .map(|(chunk, predicate)| {
+                        scope.spawn(move || {
+                            Python::with_gil(|py| {
+                                let predicate = predicate.bind(py);
+                                let mut kept = Vec::new();
+                                for msg in chunk {
+                                    let result = predicate.call1((msg.clone(),))?;
+                                    if result.is_truthy()? {
+                                        kept.push(msg);
+                                    }
+                                }
+                                Ok(kept)
+                            })
+                        })
+                    })
+                    .collect();
+                handles.into_iter().map(|h| h.join().expect("worker thread panicked")).collect()
+            })
+        });
+
+        let mut filtered = Vec::new();
+        for batch in results {
+            filtered.extend(batch?);
+        }
+        Ok(filtered)
+    }
+
     /// Get the conversation tree structure.
     /// 
     /// Returns:
@@ -498,43 +833,158 @@ impl Session {
         path
     }
     
-    /// Get all conversation threads.
-    /// 
+    /// Enumerate the true tree shape as one `Thread` per distinct leaf,
+    /// rather than flat root-to-leaf paths with duplicated prefixes.
+    ///
+    /// Args:
+    ///     sort_field: "date" (timestamp) or "text" (message text), applied
+    ///         to both sibling ordering at each branch point and the returned
+    ///         thread list
+    ///     sort_order: "asc" or "desc"
+    ///
     /// Returns:
-    ///     List[List[Message]]: All threads in the conversation
-    fn get_all_threads(&self) -> Vec<Vec<Message>> {
-        let mut threads = Vec::new();
-        let mut processed_uuids = std::collections::HashSet::new();
-        
-        // Find all leaf messages (messages with no children)
-        let parent_uuids: std::collections::HashSet<String> = self.messages.iter()
-            .filter_map(|msg| msg.parent_uuid.as_ref())
-            .cloned()
-            .collect();
-        
-        let leaf_messages: Vec<&Message> = self.messages.iter()
-            .filter(|msg| !parent_uuids.contains(&msg.uuid))
+    ///     List[Thread]: One Thread per leaf, deepest branch info included
+    #[pyo3(signature = (sort_field="date", sort_order="asc"))]
+    fn get_all_threads(&self, sort_field: &str, sort_order: &str) -> Vec<Thread> {
+        let uuid_to_msg: HashMap<String, &Message> = self.messages.iter()
+            .map(|msg| (msg.uuid.clone(), msg))
             .collect();
-        
-        // Get thread for each leaf message
-        for leaf in leaf_messages {
-            if !processed_uuids.contains(&leaf.uuid) {
-                let thread = self.get_thread(&leaf.uuid);
-                // Mark all messages in thread as processed
-                for msg in &thread {
-                    processed_uuids.insert(msg.uuid.clone());
-                }
-                if !thread.is_empty() {
-                    threads.push(thread);
+
+        let mut children: HashMap<String, Vec<String>> = HashMap::new();
+        for msg in &self.messages {
+            if let Some(parent) = &msg.parent_uuid {
+                if uuid_to_msg.contains_key(parent) {
+                    children.entry(parent.clone()).or_default().push(msg.uuid.clone());
                 }
             }
         }
-        
+        for kids in children.values_mut() {
+            sort_uuids_by_field(kids, &uuid_to_msg, sort_field, sort_order);
+        }
+
+        let roots: Vec<&Message> = self.messages.iter()
+            .filter(|msg| msg.parent_uuid.as_ref().map_or(true, |p| !uuid_to_msg.contains_key(p)))
+            .collect();
+
+        let mut threads = Vec::new();
+        for root in &roots {
+            collect_threads(&root.uuid, &mut vec![root.uuid.clone()], &mut Vec::new(), &children, &uuid_to_msg, &mut threads);
+        }
+
+        sort_threads_by_field(&mut threads, sort_field, sort_order);
         threads
     }
     
+    /// Pair each `ToolUse` block with its matching `ToolResult`, correlated
+    /// by `tool_use_id`, across the whole session.
+    ///
+    /// Returns:
+    ///     List[ToolInteraction]: One entry per tool call that received a result
+    ///
+    /// Example:
+    ///     >>> failed = [i for i in session.get_tool_interactions() if i.is_error]
+    ///     >>> for i in failed:
+    ///     ...     print(f"{i.tool_name} failed after {i.latency_ms}ms")
+    fn get_tool_interactions(&self) -> Vec<ToolInteraction> {
+        extract_tool_interactions(&self.inner.messages)
+    }
+
+    /// Detect parallel and chained tool calls across the session.
+    ///
+    /// Returns:
+    ///     ToolUsageAnalysis: parallel-call counts and multi-step tool chains
+    ///
+    /// Example:
+    ///     >>> analysis = session.analyze_tool_usage()
+    ///     >>> print(analysis.max_parallel_tool_calls)
+    ///     >>> for chain in analysis.chains:
+    ///     ...     print(chain.tools)  # e.g. ["Read", "Edit", "Bash"]
+    fn analyze_tool_usage(&self) -> ToolUsageAnalysis {
+        let main_chain = self.get_main_chain();
+
+        let mut max_parallel_tool_calls = 0;
+        let mut parallel_call_turns = 0;
+        for msg in &main_chain {
+            let tool_blocks_in_msg = msg.content_blocks.iter()
+                .filter(|c| matches!(c, ContentBlock::ToolUse { .. }))
+                .count();
+            max_parallel_tool_calls = max_parallel_tool_calls.max(tool_blocks_in_msg);
+            if tool_blocks_in_msg > 1 {
+                parallel_call_turns += 1;
+            }
+        }
+
+        // Maximal runs of consecutive tool-using assistant turns, walking the
+        // main chain in message order and grouping adjacent ones. Each
+        // run's `tools` is the ordered sequence of tool names across its turns.
+        let mut chains: Vec<(usize, Vec<String>)> = Vec::new();
+        let mut current_turns = 0usize;
+        let mut current_tools: Vec<String> = Vec::new();
+        for msg in main_chain.iter().filter(|m| m.role == "assistant") {
+            if msg.has_tool_use() {
+                current_turns += 1;
+                current_tools.extend(msg.tools.iter().cloned());
+            } else if current_turns > 0 {
+                chains.push((current_turns, std::mem::take(&mut current_tools)));
+                current_turns = 0;
+            }
+        }
+        if current_turns > 0 {
+            chains.push((current_turns, current_tools));
+        }
+
+        ToolUsageAnalysis {
+            max_parallel_tool_calls,
+            parallel_call_turns,
+            chains: chains.into_iter()
+                .filter(|(turns, _)| *turns > 1)
+                .map(|(length, tools)| ToolCallChain { length, tools })
+                .collect(),
+        }
+    }
+
+    /// Attribute cost by tool, model, and role using each turn's actual
+    /// token cost rather than splitting the session total evenly by usage
+    /// count.
+    ///
+    /// Returns:
+    ///     Dict[str, Any]: `{"by_tool": ..., "by_model": ..., "by_role": ...}`,
+    ///         each a dict mapping name to USD cost
+    fn cost_breakdown(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let dict = pyo3::types::PyDict::new_bound(py);
+
+        let by_tool = pyo3::types::PyDict::new_bound(py);
+        for (tool, cost) in cost_by_tool(&self.messages) {
+            by_tool.set_item(tool, cost)?;
+        }
+        dict.set_item("by_tool", by_tool)?;
+
+        let by_model = pyo3::types::PyDict::new_bound(py);
+        let mut model_totals: HashMap<String, f64> = HashMap::new();
+        for msg in &self.messages {
+            let model = msg.model.clone().unwrap_or_else(|| "unknown".to_string());
+            *model_totals.entry(model).or_insert(0.0) += msg.cost.unwrap_or(0.0);
+        }
+        for (model, cost) in model_totals {
+            by_model.set_item(model, cost)?;
+        }
+        dict.set_item("by_model", by_model)?;
+
+        let by_role = pyo3::types::PyDict::new_bound(py);
+        let mut role_totals: HashMap<String, f64> = HashMap::new();
+        for msg in &self.messages {
+            *role_totals.entry(msg.role.clone()).or_insert(0.0) += msg.cost.unwrap_or(0.0);
+        }
+        for (role, cost) in role_totals {
+            by_role.set_item(role, cost)?;
+        }
+        dict.set_item("by_role", by_role)?;
+
+        Ok(dict.into())
+    }
+
     /// Calculate various session metrics.
-    /// 
+    ///
     /// Returns:
     ///     Dict[str, Any]: Dictionary of calculated metrics
     fn calculate_metrics(&self, py: Python<'_>) -> PyResult<PyObject> {
@@ -553,7 +1003,13 @@ impl Session {
         // Tool metrics
         dict.set_item("unique_tools_used", self.tools_used.len())?;
         dict.set_item("total_tool_calls", self.inner.metadata.total_tool_calls)?;
-        
+
+        // Parallel/chained tool-call metrics
+        let tool_usage = self.analyze_tool_usage();
+        dict.set_item("max_parallel_tool_calls", tool_usage.max_parallel_tool_calls)?;
+        dict.set_item("parallel_call_turns", tool_usage.parallel_call_turns)?;
+        dict.set_item("tool_call_chains", tool_usage.chains.len())?;
+
         // Token metrics
         dict.set_item("total_input_tokens", self.inner.metadata.total_input_tokens)?;
         dict.set_item("total_output_tokens", self.inner.metadata.total_output_tokens)?;
@@ -563,8 +1019,10 @@ impl Session {
         // Conversation metrics
         dict.set_item("conversation_depth", self.conversation_tree.stats.max_depth)?;
         dict.set_item("conversation_branches", self.conversation_tree.stats.num_branches)?;
-        dict.set_item("sidechain_messages", 
-            self.messages.iter().filter(|m| m.is_sidechain).count())?;
+        let workers = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+        let messages = &self.messages;
+        let sidechain_messages = py.allow_threads(|| parallel_sidechain_count(messages, workers));
+        dict.set_item("sidechain_messages", sidechain_messages)?;
         
         // Duration metrics
         if let Some(duration) = self.duration {
@@ -675,6 +1133,159 @@ impl Session {
     }
 }
 
+/// Result of `Session.analyze_tool_usage()`.
+#[pyclass(name = "ToolUsageAnalysis", module = "claude_sdk")]
+#[derive(Clone)]
+pub struct ToolUsageAnalysis {
+    #[pyo3(get)]
+    pub max_parallel_tool_calls: usize,
+    #[pyo3(get)]
+    pub parallel_call_turns: usize,
+    #[pyo3(get)]
+    pub chains: Vec<ToolCallChain>,
+}
+
+/// A maximal run of consecutive tool-using assistant turns.
+#[pyclass(name = "ToolCallChain", module = "claude_sdk")]
+#[derive(Clone)]
+pub struct ToolCallChain {
+    #[pyo3(get)]
+    pub length: usize,
+    #[pyo3(get)]
+    pub tools: Vec<String>,
+}
+
+#[pymethods]
+impl ToolCallChain {
+    fn __repr__(&self) -> String {
+        format!("<ToolCallChain length={} tools={:?}>", self.length, self.tools)
+    }
+}
+
+/// A correlated tool call + result, as returned by `Session.get_tool_interactions()`.
+#[pyclass(name = "ToolInteraction", module = "claude_sdk")]
+#[derive(Clone)]
+pub struct ToolInteraction {
+    #[pyo3(get)]
+    pub message_uuid: String,
+    #[pyo3(get)]
+    pub tool_name: String,
+    #[pyo3(get)]
+    pub result: String,
+    #[pyo3(get)]
+    pub is_error: bool,
+    #[pyo3(get)]
+    pub latency_ms: i64,
+    input: serde_json::Value,
+}
+
+#[pymethods]
+impl ToolInteraction {
+    /// JSON input the tool was invoked with.
+    #[getter]
+    fn input(&self, py: Python<'_>) -> PyResult<PyObject> {
+        json_to_py(py, &self.input)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "<ToolInteraction tool_name='{}' is_error={} latency_ms={}>",
+            self.tool_name, self.is_error, self.latency_ms
+        )
+    }
+}
+
+/// Walk the session's messages pairing each `ToolUse` block with the
+/// `ToolResult` that references it via `tool_use_id`, regardless of how many
+/// messages separate them.
+fn extract_tool_interactions(messages: &[RustMessageRecord]) -> Vec<ToolInteraction> {
+    let mut interactions = Vec::new();
+    let mut pending: HashMap<String, (String, serde_json::Value, String, chrono::DateTime<chrono::Utc>)> =
+        HashMap::new();
+
+    for message in messages {
+        for content in &message.message.content {
+            match content {
+                ContentBlock::ToolUse { id, name, input } => {
+                    pending.insert(
+                        id.clone(),
+                        (name.clone(), input.clone(), message.uuid.to_string(), message.timestamp),
+                    );
+                }
+                ContentBlock::ToolResult { tool_use_id, content, is_error } => {
+                    if let Some((tool_name, input, message_uuid, start_time)) = pending.remove(tool_use_id) {
+                        let latency_ms = (message.timestamp - start_time).num_milliseconds().max(0);
+                        interactions.push(ToolInteraction {
+                            message_uuid,
+                            tool_name,
+                            result: content.as_ref().map(|c| c.as_text()).unwrap_or_default(),
+                            is_error: is_error.unwrap_or(false),
+                            latency_ms,
+                            input,
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    interactions
+}
+
+/// One paired `ToolUse`/`ToolResult` call, as needed by
+/// [`Project::tool_usage_report`]'s per-tool ledger: just the tool name,
+/// wall-clock duration, call start time, and error flag, without the
+/// input/output payloads [`ToolInteraction`] carries.
+struct ToolTiming {
+    tool_name: String,
+    duration: ChronoDuration,
+    started_at: DateTime<Utc>,
+    is_error: bool,
+}
+
+/// Like [`extract_tool_interactions`], but stripped down to what
+/// `tool_usage_report`'s aggregation needs.
+fn extract_tool_timings(messages: &[RustMessageRecord]) -> Vec<ToolTiming> {
+    let mut timings = Vec::new();
+    let mut pending: HashMap<String, (String, DateTime<Utc>)> = HashMap::new();
+
+    for message in messages {
+        for content in &message.message.content {
+            match content {
+                ContentBlock::ToolUse { id, name, .. } => {
+                    pending.insert(id.clone(), (name.clone(), message.timestamp));
+                }
+                ContentBlock::ToolResult { tool_use_id, is_error, .. } => {
+                    if let Some((tool_name, started_at)) = pending.remove(tool_use_id) {
+                        timings.push(ToolTiming {
+                            tool_name,
+                            duration: message.timestamp - started_at,
+                            started_at,
+                            is_error: is_error.unwrap_or(false),
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    timings
+}
+
+/// Running per-tool totals kept by [`Project::tool_usage_report`] while it
+/// walks every session once, like a time-entry ledger: one row per tool,
+/// updated in place as calls are found rather than collected up front and
+/// summed afterward.
+struct ToolLedgerEntry {
+    call_count: usize,
+    total_duration: ChronoDuration,
+    error_count: usize,
+    first_used: DateTime<Utc>,
+    last_used: DateTime<Utc>,
+}
+
 // Helper function to extract tool executions from messages
 fn extract_tool_executions(messages: &[RustMessageRecord]) -> Vec<crate::python::models::ToolExecution> {
     use std::collections::HashMap;
@@ -724,6 +1335,229 @@ fn extract_tool_executions(messages: &[RustMessageRecord]) -> Vec<crate::python:
     tool_executions
 }
 
+/// Burn-rate and exhaustion projection from `Project.budget_analysis()`.
+///
+/// `average_daily_cost` is spread over the full calendar span between the
+/// earliest and latest message (`days_elapsed`), including idle days with no
+/// spend, rather than only the days that happen to have entries.
+#[pyclass(name = "BudgetAnalysis", module = "claude_sdk")]
+#[derive(Clone)]
+pub struct BudgetAnalysis {
+    #[pyo3(get)]
+    pub total_cost: f64,
+    #[pyo3(get)]
+    pub days_elapsed: i64,
+    #[pyo3(get)]
+    pub average_daily_cost: f64,
+    #[pyo3(get)]
+    pub remaining: f64,
+    projected_exhaustion_date: Option<DateTime<Utc>>,
+}
+
+#[pymethods]
+impl BudgetAnalysis {
+    /// Date the budget is projected to run out at the current burn rate, or
+    /// `None` if there's been no spend to project from.
+    #[getter]
+    fn projected_exhaustion_date(&self, py: Python<'_>) -> PyResult<PyObject> {
+        match self.projected_exhaustion_date {
+            Some(dt) => datetime_to_py(py, dt),
+            None => Ok(py.None()),
+        }
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "<BudgetAnalysis total_cost=${:.4} average_daily_cost=${:.4} remaining=${:.4}>",
+            self.total_cost, self.average_daily_cost, self.remaining
+        )
+    }
+}
+
+/// Aggregate call/duration/error stats for one tool, as returned (in bulk)
+/// by `Project.tool_usage_report()`.
+#[pyclass(name = "ToolStats", module = "claude_sdk")]
+#[derive(Clone)]
+pub struct ToolStats {
+    #[pyo3(get)]
+    pub tool_name: String,
+    #[pyo3(get)]
+    pub call_count: usize,
+    #[pyo3(get)]
+    pub total_duration_seconds: f64,
+    #[pyo3(get)]
+    pub mean_duration_seconds: f64,
+    #[pyo3(get)]
+    pub error_count: usize,
+    #[pyo3(get)]
+    pub error_rate: f64,
+    first_used: DateTime<Utc>,
+    last_used: DateTime<Utc>,
+}
+
+#[pymethods]
+impl ToolStats {
+    /// Timestamp of this tool's earliest recorded call across the project.
+    #[getter]
+    fn first_used(&self, py: Python<'_>) -> PyResult<PyObject> {
+        datetime_to_py(py, self.first_used)
+    }
+
+    /// Timestamp of this tool's most recent recorded call across the project.
+    #[getter]
+    fn last_used(&self, py: Python<'_>) -> PyResult<PyObject> {
+        datetime_to_py(py, self.last_used)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "<ToolStats tool_name='{}' call_count={} total_duration_seconds={:.3} error_rate={:.2}>",
+            self.tool_name, self.call_count, self.total_duration_seconds, self.error_rate
+        )
+    }
+}
+
+/// Result of `Project.tool_usage_report()`: per-tool stats keyed by name,
+/// plus the same stats sorted by total wall-clock duration (descending) so
+/// the tools that dominate a project's runtime sort to the front.
+#[pyclass(name = "ToolUsageReport", module = "claude_sdk")]
+#[derive(Clone)]
+pub struct ToolUsageReport {
+    #[pyo3(get)]
+    pub by_tool: HashMap<String, ToolStats>,
+    #[pyo3(get)]
+    pub sorted_by_total_duration: Vec<ToolStats>,
+}
+
+#[pymethods]
+impl ToolUsageReport {
+    fn __repr__(&self) -> String {
+        format!("<ToolUsageReport tools={}>", self.by_tool.len())
+    }
+}
+
+/// A session file's on-disk fingerprint as of the last directory scan.
+///
+/// [`Project::refresh`] compares a fresh listing against the stored timeline
+/// by `session_id`, `modified`, and `size` to decide whether a file is new,
+/// changed, or gone, without re-parsing files that haven't changed.
+#[derive(Debug, Clone)]
+struct SessionDescriptor {
+    path: PathBuf,
+    session_id: String,
+    modified: SystemTime,
+    size: u64,
+}
+
+/// One change [`Project::refresh`] detected between the previous timeline
+/// and a fresh scan of the project directory.
+#[derive(Debug)]
+enum Delta {
+    Added(Session),
+    Modified(Session),
+    Removed(String),
+}
+
+/// List `*.jsonl` session files directly under `dir` with their mtime/size,
+/// mirroring the file discovery in [`crate::execution::observer::DefaultSessionLocator`]
+/// but keeping every match rather than just the most recent.
+fn scan_session_descriptors(dir: &Path) -> std::io::Result<Vec<SessionDescriptor>> {
+    let mut descriptors = Vec::new();
+
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if path.extension().map(|ext| ext == "jsonl").unwrap_or(false) {
+            let Some(session_id) = path.file_stem().and_then(|s| s.to_str()) else { continue };
+            let meta = entry.metadata()?;
+            descriptors.push(SessionDescriptor {
+                path: path.clone(),
+                session_id: session_id.to_string(),
+                modified: meta.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+                size: meta.len(),
+            });
+        }
+    }
+
+    Ok(descriptors)
+}
+
+/// Parse a single session file, discarding it (rather than failing the
+/// whole refresh) if it's unreadable or mid-write.
+fn parse_session_file(path: &Path) -> Option<Session> {
+    crate::parser::SessionParser::new(path)
+        .parse()
+        .ok()
+        .map(Session::from_rust_session)
+}
+
+/// Decide the [`Delta`]s and the next timeline for [`Project::refresh`],
+/// comparing `fresh` (a directory listing taken just now) against
+/// `previous` (the timeline as of the last refresh), keyed by `session_id`.
+/// Pulled out of `refresh` itself so this decision logic — added / modified
+/// / removed / parse-failure — can be unit tested without a live `Project`
+/// or Python GIL.
+fn diff_timeline(
+    mut previous: HashMap<String, SessionDescriptor>,
+    fresh: Vec<SessionDescriptor>,
+) -> (Vec<Delta>, Vec<SessionDescriptor>) {
+    let mut deltas = Vec::new();
+    let mut new_timeline = Vec::with_capacity(fresh.len());
+
+    for descriptor in fresh {
+        match previous.remove(&descriptor.session_id) {
+            None => match parse_session_file(&descriptor.path) {
+                Some(session) => {
+                    deltas.push(Delta::Added(session));
+                    new_timeline.push(descriptor);
+                }
+                // Parse failed — most likely the file is still mid-write.
+                // Don't record it in the new timeline at all, so the next
+                // `refresh()` still sees it as unseen and retries the
+                // parse instead of silently dropping the session forever.
+                None => {}
+            },
+            Some(prev) if prev.modified != descriptor.modified || prev.size != descriptor.size => {
+                match parse_session_file(&descriptor.path) {
+                    Some(session) => {
+                        deltas.push(Delta::Modified(session));
+                        new_timeline.push(descriptor);
+                    }
+                    // Parse failed on a file that just changed — keep the
+                    // *old* descriptor so the mtime/size mismatch is still
+                    // there next time, and `refresh()` keeps retrying
+                    // instead of adopting a file it never parsed.
+                    None => new_timeline.push(prev),
+                }
+            }
+            Some(prev) => new_timeline.push(prev),
+        }
+    }
+
+    for session_id in previous.into_keys() {
+        deltas.push(Delta::Removed(session_id));
+    }
+
+    (deltas, new_timeline)
+}
+
+/// Build the initial timeline for `sessions` by matching each session's id
+/// against a scan of `dir`. Sessions with no matching file on disk (e.g.
+/// constructed in-memory by tests) are simply absent from the timeline, so
+/// the next `refresh()` will pick them up as `Added` once a real file backs them.
+fn build_timeline(dir: &Path, sessions: &[Session]) -> Vec<SessionDescriptor> {
+    let mut by_id: HashMap<String, SessionDescriptor> = scan_session_descriptors(dir)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|d| (d.session_id.clone(), d))
+        .collect();
+
+    sessions
+        .iter()
+        .filter_map(|s| by_id.remove(&s.session_id))
+        .collect()
+}
+
 /// Container for a Claude Code project with multiple sessions.
 /// 
 /// This class represents a Claude Code project directory containing multiple
@@ -778,6 +1612,11 @@ pub struct Project {
     pub session_count: usize,
     // Keep inner Rust project for efficient operations
     inner: Option<crate::types::Project>,
+    // Session file descriptors for the sessions in `sessions_py` that have a
+    // matching file on disk, not necessarily in list order. `refresh()` diffs
+    // a fresh scan against this to
+    // patch `sessions_py` instead of rebuilding it from scratch.
+    timeline: Vec<SessionDescriptor>,
 }
 
 #[pymethods]
@@ -865,50 +1704,58 @@ impl Project {
     }
     
     /// Get sessions within a date range.
-    /// 
+    ///
+    /// Parses `start`/`end` and each session's start/end into real
+    /// `DateTime<Utc>` instants rather than comparing ISO-8601 strings
+    /// lexicographically (which breaks across offsets and fractional-second
+    /// formatting), then selects sessions whose `[session_start,
+    /// session_end]` interval genuinely overlaps `[start, end]`.
+    ///
     /// Args:
     ///     start: Start date (datetime object or ISO string)
     ///     end: End date (datetime object or ISO string)
-    /// 
+    ///     tz: Optional IANA timezone name. If given, `start`/`end` are
+    ///         treated as local wall-clock values in that timezone instead
+    ///         of via their own `tzinfo` (or the interpreter's local
+    ///         timezone for naive values)
+    ///
     /// Returns:
     ///     List[Session]: Sessions that overlap with the date range
-    fn get_sessions_by_date_range(&self, py: Python<'_>, start: &Bound<'_, PyAny>, end: &Bound<'_, PyAny>) -> PyResult<PyObject> {
-        // Convert start and end to ISO strings for comparison
-        let start_str = if let Ok(s) = start.extract::<String>() {
-            s
-        } else {
-            // Assume it's a datetime object
-            start.call_method0("isoformat")?.extract::<String>()?
-        };
-        
-        let end_str = if let Ok(s) = end.extract::<String>() {
-            s
-        } else {
-            // Assume it's a datetime object
-            end.call_method0("isoformat")?.extract::<String>()?
-        };
-        
+    #[pyo3(signature = (start, end, tz=None))]
+    fn get_sessions_by_date_range(
+        &self,
+        py: Python<'_>,
+        start: &Bound<'_, PyAny>,
+        end: &Bound<'_, PyAny>,
+        tz: Option<&str>,
+    ) -> PyResult<PyObject> {
+        let tz_parsed: Option<Tz> = tz
+            .map(|name| {
+                name.parse::<Tz>()
+                    .map_err(|e| pyo3::exceptions::PyValueError::new_err(format!("invalid timezone '{name}': {e}")))
+            })
+            .transpose()?;
+
+        let start_bound = parse_datetime_arg_in_tz(start, tz_parsed)?;
+        let end_bound = parse_datetime_arg_in_tz(end, tz_parsed)?;
+
         let filtered = pyo3::types::PyList::empty_bound(py);
         let sessions_list = self.sessions_py.bind(py);
-        
+
         for item in sessions_list.iter() {
             let session: Bound<'_, Session> = item.extract()?;
             let session_ref = session.borrow();
-            
-            // Check if session overlaps with date range
-            if let (Some(start_time), Some(end_time)) = (session_ref.start_time(py)?, session_ref.end_time(py)?) {
-                if !start_time.is_none() && !end_time.is_none() {
-                    let session_start = start_time.call_method0(py, "isoformat")?.extract::<String>(py)?;
-                    let session_end = end_time.call_method0(py, "isoformat")?.extract::<String>(py)?;
-                    
-                    // Check if session overlaps with the range
-                    if session_start <= end_str && session_end >= start_str {
-                        filtered.append(item)?;
-                    }
+
+            if let (Some(session_start), Some(session_end)) = (
+                session_ref.inner.metadata.first_message_timestamp,
+                session_ref.inner.metadata.last_message_timestamp,
+            ) {
+                if session_start <= end_bound && session_end >= start_bound {
+                    filtered.append(item)?;
                 }
             }
         }
-        
+
         Ok(filtered.into())
     }
     
@@ -974,9 +1821,252 @@ impl Project {
         
         Ok(dict.into())
     }
-    
+
+    /// Compute burn rate and a projected exhaustion date against `limit`.
+    ///
+    /// Args:
+    ///     limit: Budget ceiling in USD
+    ///     start: Optional lower bound (datetime or ISO string) restricting which messages count
+    ///     end: Optional upper bound (datetime or ISO string)
+    ///
+    /// Returns:
+    ///     BudgetAnalysis: total/average cost, remaining budget, and projected exhaustion date
+    #[pyo3(signature = (limit, start=None, end=None))]
+    fn budget_analysis(
+        &self,
+        py: Python<'_>,
+        limit: f64,
+        start: Option<&Bound<'_, PyAny>>,
+        end: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<BudgetAnalysis> {
+        let start_bound = start.map(parse_datetime_arg).transpose()?;
+        let end_bound = end.map(parse_datetime_arg).transpose()?;
+
+        let sessions_list = self.sessions_py.bind(py);
+        let mut total_cost = 0.0;
+        let mut earliest: Option<DateTime<Utc>> = None;
+        let mut latest: Option<DateTime<Utc>> = None;
+
+        for item in sessions_list.iter() {
+            let session: Bound<'_, Session> = item.extract()?;
+            for msg in &session.borrow().messages {
+                let Ok(ts) = DateTime::parse_from_rfc3339(&msg.timestamp) else { continue };
+                let ts = ts.with_timezone(&Utc);
+                if start_bound.is_some_and(|s| ts < s) || end_bound.is_some_and(|e| ts > e) {
+                    continue;
+                }
+                total_cost += msg.cost.unwrap_or(0.0);
+                earliest = Some(earliest.map_or(ts, |e: DateTime<Utc>| e.min(ts)));
+                latest = Some(latest.map_or(ts, |l: DateTime<Utc>| l.max(ts)));
+            }
+        }
+
+        let (days_elapsed, average_daily_cost, projected_exhaustion_date) = match (earliest, latest) {
+            (Some(first), Some(last)) => {
+                let days_elapsed = (last.date_naive() - first.date_naive()).num_days() + 1;
+                let average_daily_cost = total_cost / days_elapsed as f64;
+                let remaining = limit - total_cost;
+                let projected = if average_daily_cost > 0.0 {
+                    let days_left = (remaining / average_daily_cost).ceil() as i64;
+                    Some(last + ChronoDuration::days(days_left))
+                } else {
+                    None
+                };
+                (days_elapsed, average_daily_cost, projected)
+            }
+            _ => (0, 0.0, None),
+        };
+
+        Ok(BudgetAnalysis {
+            total_cost,
+            days_elapsed,
+            average_daily_cost,
+            remaining: limit - total_cost,
+            projected_exhaustion_date,
+        })
+    }
+
+    /// Time-series cost aggregation, bucketed by day/week/month and
+    /// optionally gap-filled with zero-cost buckets.
+    ///
+    /// Unlike [`Project::calculate_daily_costs`], which buckets by slicing
+    /// the ISO timestamp string and returns an unordered dict, this parses
+    /// each timestamp into a real `DateTime`, buckets in `tz` (or UTC if
+    /// `tz` is `None`), and returns a list ordered by bucket start.
+    ///
+    /// Args:
+    ///     granularity: "day", "week" (Monday-start), or "month"
+    ///     tz: Optional IANA timezone name (e.g. "America/New_York") to bucket in; defaults to UTC
+    ///     fill_gaps: If true, emit a 0.0 entry for every bucket between the first and last with no cost
+    ///
+    /// Returns:
+    ///     List[Tuple[datetime, float]]: `(bucket_start, cost)` pairs sorted by `bucket_start`
+    #[pyo3(signature = (granularity="day", tz=None, fill_gaps=true))]
+    fn cost_series(
+        &self,
+        py: Python<'_>,
+        granularity: &str,
+        tz: Option<&str>,
+        fill_gaps: bool,
+    ) -> PyResult<Vec<(PyObject, f64)>> {
+        let tz_parsed: Option<Tz> = tz
+            .map(|name| {
+                name.parse::<Tz>()
+                    .map_err(|e| pyo3::exceptions::PyValueError::new_err(format!("invalid timezone '{name}': {e}")))
+            })
+            .transpose()?;
+
+        let sessions_list = self.sessions_py.bind(py);
+        let mut buckets: BTreeMap<DateTime<Utc>, f64> = BTreeMap::new();
+
+        for item in sessions_list.iter() {
+            let session: Bound<'_, Session> = item.extract()?;
+            for msg in &session.borrow().messages {
+                let Some(cost) = msg.cost else { continue };
+                let Ok(ts) = DateTime::parse_from_rfc3339(&msg.timestamp) else { continue };
+                let ts = ts.with_timezone(&Utc);
+                let bucket_start = match tz_parsed {
+                    Some(tz) => truncate_to_bucket(ts.with_timezone(&tz), granularity).with_timezone(&Utc),
+                    None => truncate_to_bucket(ts, granularity),
+                };
+                *buckets.entry(bucket_start).or_insert(0.0) += cost;
+            }
+        }
+
+        if fill_gaps {
+            if let (Some(&first), Some(&last)) = (buckets.keys().next(), buckets.keys().last()) {
+                let mut cursor = first;
+                while cursor <= last {
+                    buckets.entry(cursor).or_insert(0.0);
+                    cursor = match tz_parsed {
+                        Some(tz) => next_bucket(cursor.with_timezone(&tz), granularity).with_timezone(&Utc),
+                        None => next_bucket(cursor, granularity),
+                    };
+                }
+            }
+        }
+
+        // `buckets` is keyed by UTC instant throughout (needed for correct
+        // ordering and for `next_bucket` to step consistently across DST
+        // changes), but a caller who passed `tz` wants bucket boundaries
+        // back as the local wall clock they asked for — a UTC "day" bucket
+        // for `America/New_York` would otherwise come back as 05:00:00, not
+        // local midnight. Convert to `tz` right before building the Python
+        // value so `datetime_to_py` reads wall-clock fields from the local
+        // zone instead of UTC.
+        buckets
+            .into_iter()
+            .map(|(bucket_start, cost)| {
+                let py_dt = match tz_parsed {
+                    Some(tz) => datetime_to_py(py, bucket_start.with_timezone(&tz))?,
+                    None => datetime_to_py(py, bucket_start)?,
+                };
+                Ok((py_dt, cost))
+            })
+            .collect()
+    }
+
+    /// Re-scan the project directory and patch in only what changed since
+    /// construction or the last `refresh()`, instead of reloading every
+    /// `Session` from scratch.
+    ///
+    /// Compares a fresh directory listing against the stored timeline by
+    /// `session_id`, modification time, and size, producing a `Delta` per
+    /// session file that was added, modified, or removed. Each delta is
+    /// applied to the `sessions` list in place (append, replace, or remove)
+    /// and `total_cost`/`total_messages`/`session_count` are adjusted by the
+    /// delta rather than re-summed from every session, so only changed
+    /// session files are re-parsed.
+    ///
+    /// Returns:
+    ///     Tuple[int, int, int]: counts of sessions `(added, modified, removed)`
+    fn refresh(&mut self, py: Python<'_>) -> PyResult<(usize, usize, usize)> {
+        let Some(project_dir) = self.inner.as_ref().map(|p| p.project_path.clone()) else {
+            return Ok((0, 0, 0));
+        };
+
+        let fresh = scan_session_descriptors(&project_dir)
+            .map_err(|e| pyo3::exceptions::PyOSError::new_err(e.to_string()))?;
+
+        let previous: HashMap<String, SessionDescriptor> = self
+            .timeline
+            .drain(..)
+            .map(|d| (d.session_id.clone(), d))
+            .collect();
+
+        let (deltas, new_timeline) = diff_timeline(previous, fresh);
+
+        let counts = self.apply_deltas(py, deltas)?;
+        self.timeline = new_timeline;
+        Ok(counts)
+    }
+
+    /// Aggregate tool call count, wall-clock duration, and error rate per
+    /// tool name across every session in the project.
+    ///
+    /// Walks each session's messages once, pairing `ToolUse`/`ToolResult`
+    /// blocks like [`extract_tool_executions`], and folds each call into a
+    /// running per-tool ledger (count, summed duration, error count,
+    /// first/last use) rather than collecting every individual call before
+    /// summing.
+    ///
+    /// Returns:
+    ///     ToolUsageReport: per-tool stats, both as a dict (`by_tool`) and
+    ///         sorted by total duration descending (`sorted_by_total_duration`)
+    fn tool_usage_report(&self, py: Python<'_>) -> PyResult<ToolUsageReport> {
+        let sessions_list = self.sessions_py.bind(py);
+        let mut ledger: BTreeMap<String, ToolLedgerEntry> = BTreeMap::new();
+
+        for item in sessions_list.iter() {
+            let session: Bound<'_, Session> = item.extract()?;
+            for timing in extract_tool_timings(&session.borrow().inner.messages) {
+                let entry = ledger.entry(timing.tool_name).or_insert_with(|| ToolLedgerEntry {
+                    call_count: 0,
+                    total_duration: ChronoDuration::zero(),
+                    error_count: 0,
+                    first_used: timing.started_at,
+                    last_used: timing.started_at,
+                });
+                entry.call_count += 1;
+                entry.total_duration = entry.total_duration + timing.duration;
+                if timing.is_error {
+                    entry.error_count += 1;
+                }
+                entry.first_used = entry.first_used.min(timing.started_at);
+                entry.last_used = entry.last_used.max(timing.started_at);
+            }
+        }
+
+        let by_tool: HashMap<String, ToolStats> = ledger
+            .into_iter()
+            .map(|(tool_name, entry)| {
+                let total_duration_seconds = entry.total_duration.num_milliseconds() as f64 / 1000.0;
+                let stats = ToolStats {
+                    call_count: entry.call_count,
+                    total_duration_seconds,
+                    mean_duration_seconds: total_duration_seconds / entry.call_count as f64,
+                    error_count: entry.error_count,
+                    error_rate: entry.error_count as f64 / entry.call_count as f64,
+                    first_used: entry.first_used,
+                    last_used: entry.last_used,
+                    tool_name: tool_name.clone(),
+                };
+                (tool_name, stats)
+            })
+            .collect();
+
+        let mut sorted_by_total_duration: Vec<ToolStats> = by_tool.values().cloned().collect();
+        sorted_by_total_duration.sort_by(|a, b| {
+            b.total_duration_seconds
+                .partial_cmp(&a.total_duration_seconds)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        Ok(ToolUsageReport { by_tool, sorted_by_total_duration })
+    }
+
     /// Convert project to a dictionary.
-    /// 
+    ///
     /// Returns:
     ///     Dict[str, Any]: Dictionary representation of the project
     fn to_dict(&self, py: Python<'_>) -> PyResult<PyObject> {
@@ -1036,14 +2126,15 @@ impl Project {
         let total_cost = sessions.iter().map(|s| s.total_cost).sum();
         let total_messages = sessions.iter().map(|s| s.messages.len()).sum();
         let session_count = sessions.len();
-        
+        let timeline = build_timeline(&path, &sessions);
+
         // Create Python list of sessions
         let sessions_list = pyo3::types::PyList::empty_bound(py);
         for session in sessions {
             let session_obj = Py::new(py, session)?;
             sessions_list.append(session_obj)?;
         }
-        
+
         Ok(Project {
             name,
             path: path_obj.into(),
@@ -1052,6 +2143,7 @@ impl Project {
             total_messages,
             session_count,
             inner,
+            timeline,
         })
     }
     
@@ -1063,7 +2155,224 @@ impl Project {
         
         let name = project.name.clone();
         let path = project.project_path.clone();
-        
+
         Self::new(py, name, path, sessions, Some(project))
     }
+
+    /// Apply a batch of [`Delta`]s to `sessions_py` in place, adjusting the
+    /// running totals by each delta instead of re-summing every session.
+    /// Returns `(added, modified, removed)` counts.
+    fn apply_deltas(&mut self, py: Python<'_>, deltas: Vec<Delta>) -> PyResult<(usize, usize, usize)> {
+        let sessions_list = self.sessions_py.bind(py);
+        let (mut added, mut modified, mut removed) = (0, 0, 0);
+
+        for delta in deltas {
+            match delta {
+                Delta::Added(session) => {
+                    self.total_cost += session.total_cost;
+                    self.total_messages += session.messages.len();
+                    self.session_count += 1;
+                    sessions_list.append(Py::new(py, session)?)?;
+                    added += 1;
+                }
+                Delta::Modified(session) => {
+                    let Some(index) = find_session_index(sessions_list, &session.session_id)? else {
+                        continue;
+                    };
+                    let old: Bound<'_, Session> = sessions_list.get_item(index)?.extract()?;
+                    let (old_cost, old_messages) = {
+                        let old_ref = old.borrow();
+                        (old_ref.total_cost, old_ref.messages.len())
+                    };
+                    self.total_cost += session.total_cost - old_cost;
+                    self.total_messages = self.total_messages + session.messages.len() - old_messages;
+                    sessions_list.set_item(index, Py::new(py, session)?)?;
+                    modified += 1;
+                }
+                Delta::Removed(session_id) => {
+                    let Some(index) = find_session_index(sessions_list, &session_id)? else {
+                        continue;
+                    };
+                    let old: Bound<'_, Session> = sessions_list.get_item(index)?.extract()?;
+                    let old_ref = old.borrow();
+                    self.total_cost -= old_ref.total_cost;
+                    self.total_messages -= old_ref.messages.len();
+                    drop(old_ref);
+                    sessions_list.del_item(index)?;
+                    self.session_count -= 1;
+                    removed += 1;
+                }
+            }
+        }
+
+        Ok((added, modified, removed))
+    }
+}
+
+/// Index of the session with `session_id` in `sessions_list`, if present.
+fn find_session_index(
+    sessions_list: &Bound<'_, pyo3::types::PyList>,
+    session_id: &str,
+) -> PyResult<Option<usize>> {
+    for (index, item) in sessions_list.iter().enumerate() {
+        let session: Bound<'_, Session> = item.extract()?;
+        if session.borrow().session_id == session_id {
+            return Ok(Some(index));
+        }
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono_tz::America::New_York;
+
+    fn ymd_hms(y: i32, mo: u32, d: u32, h: u32, mi: u32, s: u32) -> DateTime<Tz> {
+        New_York
+            .from_local_datetime(&NaiveDate::from_ymd_opt(y, mo, d).unwrap().and_hms_opt(h, mi, s).unwrap())
+            .single()
+            .unwrap()
+    }
+
+    #[test]
+    fn truncate_to_bucket_day_is_idempotent_and_local_midnight() {
+        let dt = ymd_hms(2026, 3, 15, 17, 42, 9);
+        let truncated = truncate_to_bucket(dt, "day");
+        assert_eq!((truncated.hour(), truncated.minute(), truncated.second()), (0, 0, 0));
+        assert_eq!(truncated.day(), 15);
+        assert_eq!(truncate_to_bucket(truncated, "day"), truncated);
+    }
+
+    #[test]
+    fn truncate_to_bucket_week_starts_monday() {
+        // 2026-03-19 is a Thursday.
+        let dt = ymd_hms(2026, 3, 19, 10, 0, 0);
+        let truncated = truncate_to_bucket(dt, "week");
+        assert_eq!((truncated.year(), truncated.month(), truncated.day()), (2026, 3, 16));
+        assert_eq!(truncated.weekday().num_days_from_monday(), 0);
+    }
+
+    #[test]
+    fn truncate_to_bucket_month_resets_to_first() {
+        let dt = ymd_hms(2026, 3, 19, 10, 0, 0);
+        let truncated = truncate_to_bucket(dt, "month");
+        assert_eq!((truncated.year(), truncated.month(), truncated.day()), (2026, 3, 1));
+    }
+
+    #[test]
+    fn truncate_to_bucket_across_spring_forward_dst_gap() {
+        // America/New_York springs forward at 2026-03-08 02:00 -> 03:00; a
+        // day bucket's local midnight itself isn't in the gap, so this just
+        // pins down that truncation survives a day straddling the change.
+        let dt = ymd_hms(2026, 3, 8, 14, 30, 0);
+        let truncated = truncate_to_bucket(dt, "day");
+        assert_eq!((truncated.year(), truncated.month(), truncated.day()), (2026, 3, 8));
+        assert_eq!((truncated.hour(), truncated.minute()), (0, 0));
+    }
+
+    #[test]
+    fn next_bucket_day_steps_across_spring_forward() {
+        // 2026-03-08 is the DST start date for America/New_York; the next
+        // day bucket should land on 2026-03-09 local midnight, not be thrown
+        // off by the missing 02:00-03:00 hour.
+        let dt = truncate_to_bucket(ymd_hms(2026, 3, 8, 0, 0, 0), "day");
+        let next = next_bucket(dt, "day");
+        assert_eq!((next.year(), next.month(), next.day()), (2026, 3, 9));
+    }
+
+    #[test]
+    fn next_bucket_day_steps_across_fall_back() {
+        // America/New_York falls back at 2026-11-01 02:00 -> 01:00.
+        let dt = truncate_to_bucket(ymd_hms(2026, 10, 31, 0, 0, 0), "day");
+        let next = next_bucket(dt, "day");
+        assert_eq!((next.year(), next.month(), next.day()), (2026, 11, 1));
+    }
+
+    #[test]
+    fn next_bucket_month_rolls_over_year() {
+        let dt = truncate_to_bucket(ymd_hms(2026, 12, 10, 0, 0, 0), "month");
+        let next = next_bucket(dt, "month");
+        assert_eq!((next.year(), next.month(), next.day()), (2027, 1, 1));
+    }
+
+    #[test]
+    fn next_bucket_week_advances_by_seven_days() {
+        let dt = truncate_to_bucket(ymd_hms(2026, 3, 19, 0, 0, 0), "week");
+        let next = next_bucket(dt, "week");
+        assert_eq!((next.year(), next.month(), next.day()), (2026, 3, 23));
+    }
+
+    fn descriptor(session_id: &str, path: &Path, modified: SystemTime, size: u64) -> SessionDescriptor {
+        SessionDescriptor {
+            path: path.to_path_buf(),
+            session_id: session_id.to_string(),
+            modified,
+            size,
+        }
+    }
+
+    #[test]
+    fn diff_timeline_drops_new_file_that_fails_to_parse() {
+        // A brand-new session file that doesn't parse (e.g. still mid-write)
+        // must not be recorded in the new timeline at all, so the next
+        // refresh() still treats it as unseen and retries.
+        let missing = PathBuf::from("/nonexistent/claude-sdk-test-session.jsonl");
+        let fresh = vec![descriptor("new-session", &missing, SystemTime::now(), 123)];
+
+        let (deltas, new_timeline) = diff_timeline(HashMap::new(), fresh);
+
+        assert!(deltas.is_empty());
+        assert!(new_timeline.is_empty());
+    }
+
+    #[test]
+    fn diff_timeline_keeps_old_descriptor_when_changed_file_fails_to_parse() {
+        // A previously-seen file whose mtime/size changed but which still
+        // fails to parse must keep the *old* descriptor, so the mismatch
+        // persists and the next refresh() keeps retrying instead of
+        // silently adopting a file it never parsed.
+        let missing = PathBuf::from("/nonexistent/claude-sdk-test-session.jsonl");
+        let old = descriptor("flaky-session", &missing, SystemTime::UNIX_EPOCH, 10);
+        let mut previous = HashMap::new();
+        previous.insert("flaky-session".to_string(), old.clone());
+
+        let fresh = vec![descriptor("flaky-session", &missing, SystemTime::now(), 99)];
+        let (deltas, new_timeline) = diff_timeline(previous, fresh);
+
+        assert!(deltas.is_empty());
+        assert_eq!(new_timeline.len(), 1);
+        assert_eq!(new_timeline[0].size, old.size);
+        assert_eq!(new_timeline[0].modified, old.modified);
+    }
+
+    #[test]
+    fn diff_timeline_emits_removed_for_missing_session() {
+        let missing = PathBuf::from("/nonexistent/claude-sdk-test-session.jsonl");
+        let mut previous = HashMap::new();
+        previous.insert(
+            "gone-session".to_string(),
+            descriptor("gone-session", &missing, SystemTime::UNIX_EPOCH, 10),
+        );
+
+        let (deltas, new_timeline) = diff_timeline(previous, Vec::new());
+
+        assert!(new_timeline.is_empty());
+        assert!(matches!(deltas.as_slice(), [Delta::Removed(id)] if id == "gone-session"));
+    }
+
+    #[test]
+    fn diff_timeline_leaves_unchanged_session_untouched() {
+        let missing = PathBuf::from("/nonexistent/claude-sdk-test-session.jsonl");
+        let unchanged = descriptor("stable-session", &missing, SystemTime::UNIX_EPOCH, 42);
+        let mut previous = HashMap::new();
+        previous.insert("stable-session".to_string(), unchanged.clone());
+
+        let fresh = vec![descriptor("stable-session", &missing, SystemTime::UNIX_EPOCH, 42)];
+        let (deltas, new_timeline) = diff_timeline(previous, fresh);
+
+        assert!(deltas.is_empty());
+        assert_eq!(new_timeline.len(), 1);
+        assert_eq!(new_timeline[0].size, unchanged.size);
+    }
 }
\ No newline at end of file
@@ -4,10 +4,15 @@ use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 
 use crate::execution::{
-    ClaudeExecution as RustClaudeExecution, ClaudePrompt as RustClaudePrompt,
-    Conversation as RustConversation, EnvironmentSnapshot as RustEnvironmentSnapshot,
-    Transition as RustTransition, Workspace as RustWorkspace,
+    ChangeKind as RustChangeKind, ClaudeExecution as RustClaudeExecution,
+    ClaudePrompt as RustClaudePrompt, Conversation as RustConversation,
+    ConversationStore as RustConversationStore, EnvironmentSnapshot as RustEnvironmentSnapshot,
+    FileChange as RustFileChange, RecordingPolicy as RustRecordingPolicy,
+    StreamEvent as RustStreamEvent, Transition as RustTransition,
+    WatchHandle as RustWatchHandle, Workspace as RustWorkspace,
 };
+use crate::types::ToolExecution as RustToolExecution;
+use crate::python::utils::{datetime_to_py, json_to_py};
 
 /// Python wrapper for Workspace
 #[pyclass(name = "Workspace")]
@@ -64,6 +69,63 @@ impl PyWorkspace {
         workspace.set_model(model);
         Ok(())
     }
+
+    /// Point session-file discovery at `dir` instead of `~/.claude/projects`,
+    /// so tests and alternate hosts can run against a fixture directory.
+    fn set_session_dir(&self, dir: String) -> PyResult<()> {
+        let mut workspace = self.inner.lock()
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("Lock error: {}", e)))?;
+        workspace.set_session_dir(PathBuf::from(dir));
+        Ok(())
+    }
+
+    /// Watch the workspace filesystem, invoking `callback(snapshot)` with a
+    /// `EnvironmentSnapshot` each time tracked files settle after a burst of
+    /// changes. Returns a `WatchHandle`; drop it or call `.stop()` to stop
+    /// watching.
+    fn watch(&self, callback: PyObject) -> PyResult<PyWatchHandle> {
+        let workspace = self.inner.lock()
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("Lock error: {}", e)))?;
+        let handle = workspace
+            .watch(move |snapshot| {
+                Python::with_gil(|py| {
+                    let py_snapshot = PyEnvironmentSnapshot { inner: snapshot };
+                    if let Err(e) = callback.call1(py, (py_snapshot,)) {
+                        e.print(py);
+                    }
+                });
+            })
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+        Ok(PyWatchHandle { inner: Some(handle) })
+    }
+}
+
+/// Python wrapper for a running workspace watch.
+#[pyclass(name = "WatchHandle")]
+pub struct PyWatchHandle {
+    inner: Option<RustWatchHandle>,
+}
+
+#[pymethods]
+impl PyWatchHandle {
+    /// Stop watching and wait for the background thread to exit.
+    fn stop(&mut self) {
+        if let Some(handle) = self.inner.take() {
+            handle.stop();
+        }
+    }
+}
+
+/// Map the simple `record`/`strict` flags Python callers pass into a
+/// [`RustRecordingPolicy`]: `record=False` is `Disabled` regardless of
+/// `strict`, `record=True` is `BestEffort` (the historical default) unless
+/// `strict=True` asks for `Required`'s hard failure-and-verify guarantee.
+fn recording_policy(record: bool, strict: bool) -> RustRecordingPolicy {
+    match (record, strict) {
+        (false, _) => RustRecordingPolicy::Disabled,
+        (true, false) => RustRecordingPolicy::BestEffort,
+        (true, true) => RustRecordingPolicy::Required,
+    }
 }
 
 /// Python wrapper for Conversation
@@ -75,8 +137,8 @@ pub struct PyConversation {
 #[pymethods]
 impl PyConversation {
     #[new]
-    #[pyo3(signature = (workspace, record=true))]
-    fn new(workspace: &PyWorkspace, record: bool) -> PyResult<Self> {
+    #[pyo3(signature = (workspace, record=true, strict=false))]
+    fn new(workspace: &PyWorkspace, record: bool, strict: bool) -> PyResult<Self> {
         // Need to clone the Arc<Mutex<>> for conversation, but conversation expects Arc<Workspace>
         // We'll need to restructure this, but for now let's create a new pattern
         let workspace_path = {
@@ -84,18 +146,14 @@ impl PyConversation {
                 .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("Lock error: {}", e)))?;
             ws.path().clone()
         };
-        
+
         // Create a new workspace for the conversation - this is a limitation we can improve later
         let rust_workspace = RustWorkspace::new(workspace_path)
             .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
         let workspace_arc = Arc::new(rust_workspace);
-        
-        let conversation = if record {
-            RustConversation::new_with_options(workspace_arc, true)
-                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?
-        } else {
-            RustConversation::new(workspace_arc)
-        };
+
+        let conversation = RustConversation::new_with_options(workspace_arc, recording_policy(record, strict))
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
         Ok(Self {
             inner: conversation,
         })
@@ -109,6 +167,26 @@ impl PyConversation {
         Ok(PyTransition { inner: transition })
     }
 
+    /// Send a message, invoking `on_event(event)` with a `StreamEvent` as
+    /// partial assistant text, tool use and results, file edits, token usage
+    /// and the final result (session id and cost) arrive.
+    /// Still blocks until the turn completes and returns the final
+    /// `Transition`, exactly like `send`.
+    fn send_streaming(&mut self, message: &str, on_event: PyObject) -> PyResult<PyTransition> {
+        let transition = self
+            .inner
+            .send_streaming(message, |event| {
+                Python::with_gil(|py| {
+                    let py_event = PyStreamEvent::from(event);
+                    if let Err(e) = on_event.call1(py, (py_event,)) {
+                        e.print(py);
+                    }
+                });
+            })
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+        Ok(PyTransition { inner: transition })
+    }
+
     fn history(&self) -> Vec<PyTransition> {
         self.inner
             .history()
@@ -142,6 +220,16 @@ impl PyConversation {
         self.inner.tools_used()
     }
 
+    /// Branch a new conversation off this one at its `at`-th transition,
+    /// without mutating `self`. See [`RustConversation::fork`].
+    fn fork(&self, at: usize) -> PyResult<Self> {
+        let conversation = self
+            .inner
+            .fork(at)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+        Ok(Self { inner: conversation })
+    }
+
     fn save(&self, path: &str) -> PyResult<()> {
         self.inner
             .save(&PathBuf::from(path))
@@ -149,21 +237,21 @@ impl PyConversation {
     }
 
     #[staticmethod]
-    #[pyo3(signature = (path, workspace, record=true))]
-    fn load(path: &str, workspace: &PyWorkspace, record: bool) -> PyResult<Self> {
+    #[pyo3(signature = (path, workspace, record=true, strict=false))]
+    fn load(path: &str, workspace: &PyWorkspace, record: bool, strict: bool) -> PyResult<Self> {
         // Get workspace path for creating new workspace instance
         let workspace_path = {
             let ws = workspace.inner.lock()
                 .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("Lock error: {}", e)))?;
             ws.path().clone()
         };
-        
+
         // Create a new workspace for the conversation
         let rust_workspace = RustWorkspace::new(workspace_path)
             .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
         let workspace_arc = Arc::new(rust_workspace);
-        
-        let conversation = RustConversation::load(&PathBuf::from(path), workspace_arc, record)
+
+        let conversation = RustConversation::load(&PathBuf::from(path), workspace_arc, recording_policy(record, strict))
             .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
         Ok(Self {
             inner: conversation,
@@ -224,8 +312,8 @@ impl PyTransition {
     }
 
     #[getter]
-    fn recorded_at(&self) -> String {
-        self.inner.recorded_at.to_rfc3339()
+    fn recorded_at(&self, py: Python<'_>) -> PyResult<PyObject> {
+        datetime_to_py(py, self.inner.recorded_at)
     }
 
     fn new_messages(&self) -> Vec<crate::python::classes::Message> {
@@ -241,9 +329,68 @@ impl PyTransition {
         self.inner.tools_used()
     }
 
+    /// Tool calls made during this transition, paired with their results.
+    fn tool_executions(&self) -> Vec<PyToolExecution> {
+        self.inner
+            .tool_executions()
+            .into_iter()
+            .map(|exec| PyToolExecution { inner: exec })
+            .collect()
+    }
+
     fn has_tool_errors(&self) -> bool {
         self.inner.has_tool_errors()
     }
+
+    /// Added/modified/deleted/renamed files between `before` and `after`,
+    /// collapsing same-hash add+delete pairs into renames.
+    fn diff(&self) -> Vec<PyFileChange> {
+        self.inner
+            .diff()
+            .into_iter()
+            .map(|change| PyFileChange { inner: change })
+            .collect()
+    }
+}
+
+/// Python wrapper for a single `Transition::diff()` entry.
+#[pyclass(name = "FileChange")]
+#[derive(Clone)]
+pub struct PyFileChange {
+    inner: RustFileChange,
+}
+
+#[pymethods]
+impl PyFileChange {
+    #[getter]
+    fn path(&self) -> String {
+        self.inner.path.display().to_string()
+    }
+
+    #[getter]
+    fn old_path(&self) -> Option<String> {
+        self.inner.old_path.as_ref().map(|p| p.display().to_string())
+    }
+
+    #[getter]
+    fn kind(&self) -> &'static str {
+        match self.inner.kind {
+            RustChangeKind::Added => "added",
+            RustChangeKind::Modified => "modified",
+            RustChangeKind::Deleted => "deleted",
+            RustChangeKind::Renamed => "renamed",
+        }
+    }
+
+    #[getter]
+    fn old_hash(&self) -> Option<String> {
+        self.inner.old_hash.clone()
+    }
+
+    #[getter]
+    fn new_hash(&self) -> Option<String> {
+        self.inner.new_hash.clone()
+    }
 }
 
 /// Python wrapper for ClaudePrompt
@@ -307,6 +454,166 @@ impl PyClaudeExecution {
     }
 }
 
+/// Python wrapper for a single tool call/result pair, as returned by
+/// `Transition.tool_executions()`.
+#[pyclass(name = "ToolExecution")]
+#[derive(Clone)]
+pub struct PyToolExecution {
+    inner: RustToolExecution,
+}
+
+#[pymethods]
+impl PyToolExecution {
+    #[getter]
+    fn tool_name(&self) -> &str {
+        &self.inner.tool_name
+    }
+
+    #[getter]
+    fn input(&self, py: Python<'_>) -> PyResult<PyObject> {
+        json_to_py(py, &self.inner.input)
+    }
+
+    #[getter]
+    fn output(&self) -> &str {
+        &self.inner.result.content
+    }
+
+    #[getter]
+    fn is_error(&self) -> bool {
+        self.inner.result.is_error
+    }
+
+    #[getter]
+    fn is_success(&self) -> bool {
+        self.inner.is_success()
+    }
+
+    #[getter]
+    fn duration_ms(&self) -> u64 {
+        self.inner.duration.as_millis() as u64
+    }
+
+    #[getter]
+    fn timestamp(&self, py: Python<'_>) -> PyResult<PyObject> {
+        datetime_to_py(py, self.inner.timestamp)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "<ToolExecution tool_name='{}' is_error={}>",
+            self.inner.tool_name, self.inner.result.is_error
+        )
+    }
+}
+
+/// Python wrapper for a single `StreamEvent` delivered to
+/// `PyConversation.send_streaming`'s `on_event` callback.
+///
+/// Flattened across all `StreamEvent` variants: `event_type` tells you which
+/// one arrived, and only the fields relevant to that variant are populated.
+#[pyclass(name = "StreamEvent")]
+#[derive(Clone)]
+pub struct PyStreamEvent {
+    #[pyo3(get)]
+    event_type: String,
+    #[pyo3(get)]
+    text: Option<String>,
+    #[pyo3(get)]
+    tool_id: Option<String>,
+    #[pyo3(get)]
+    tool_name: Option<String>,
+    #[pyo3(get)]
+    tool_input_json: Option<String>,
+    #[pyo3(get)]
+    tool_use_id: Option<String>,
+    #[pyo3(get)]
+    content: Option<String>,
+    #[pyo3(get)]
+    is_error: Option<bool>,
+    #[pyo3(get)]
+    path: Option<String>,
+    #[pyo3(get)]
+    input_tokens: Option<u64>,
+    #[pyo3(get)]
+    output_tokens: Option<u64>,
+    #[pyo3(get)]
+    session_id: Option<String>,
+    #[pyo3(get)]
+    cost_usd: Option<f64>,
+    #[pyo3(get)]
+    result: Option<String>,
+    #[pyo3(get)]
+    model: Option<String>,
+}
+
+impl From<RustStreamEvent> for PyStreamEvent {
+    fn from(event: RustStreamEvent) -> Self {
+        match event {
+            RustStreamEvent::AssistantText { text } => Self {
+                event_type: "assistant_text".to_string(),
+                text: Some(text),
+                ..Self::empty()
+            },
+            RustStreamEvent::ToolUseStarted { id, name, input } => Self {
+                event_type: "tool_use_started".to_string(),
+                tool_id: Some(id),
+                tool_name: Some(name),
+                tool_input_json: Some(input.to_string()),
+                ..Self::empty()
+            },
+            RustStreamEvent::ToolResult { tool_use_id, content, is_error } => Self {
+                event_type: "tool_result".to_string(),
+                tool_use_id: Some(tool_use_id),
+                content: Some(content),
+                is_error: Some(is_error),
+                ..Self::empty()
+            },
+            RustStreamEvent::FileEdited { path } => Self {
+                event_type: "file_edited".to_string(),
+                path: Some(path),
+                ..Self::empty()
+            },
+            RustStreamEvent::TokenUsage { input_tokens, output_tokens } => Self {
+                event_type: "token_usage".to_string(),
+                input_tokens: Some(input_tokens),
+                output_tokens: Some(output_tokens),
+                ..Self::empty()
+            },
+            RustStreamEvent::FinalResult { session_id, cost_usd, result, model } => Self {
+                event_type: "result".to_string(),
+                session_id: Some(session_id),
+                cost_usd: Some(cost_usd),
+                result: Some(result),
+                model,
+                ..Self::empty()
+            },
+        }
+    }
+}
+
+impl PyStreamEvent {
+    fn empty() -> Self {
+        Self {
+            event_type: String::new(),
+            text: None,
+            tool_id: None,
+            tool_name: None,
+            tool_input_json: None,
+            tool_use_id: None,
+            content: None,
+            is_error: None,
+            path: None,
+            input_tokens: None,
+            output_tokens: None,
+            session_id: None,
+            cost_usd: None,
+            result: None,
+            model: None,
+        }
+    }
+}
+
 /// Python wrapper for EnvironmentSnapshot
 #[pyclass(name = "EnvironmentSnapshot")]
 #[derive(Clone)]
@@ -320,8 +627,8 @@ impl PyEnvironmentSnapshot {
     fn files(&self) -> PyResult<Py<PyDict>> {
         Python::with_gil(|py| {
             let dict = PyDict::new(py);
-            for (path, content) in &self.inner.files {
-                dict.set_item(path.display().to_string(), content)?;
+            for (path, file) in &self.inner.files {
+                dict.set_item(path.display().to_string(), file.body.as_deref())?;
             }
             Ok(dict.into())
         })
@@ -338,8 +645,143 @@ impl PyEnvironmentSnapshot {
     }
 
     #[getter]
-    fn timestamp(&self) -> String {
-        self.inner.timestamp.to_rfc3339()
+    fn timestamp(&self, py: Python<'_>) -> PyResult<PyObject> {
+        datetime_to_py(py, self.inner.timestamp)
+    }
+}
+
+/// Python wrapper for a single `ConversationStore::list()` row.
+#[pyclass(name = "ConversationEntry")]
+pub struct PyConversationEntry {
+    #[pyo3(get)]
+    name: String,
+    #[pyo3(get)]
+    id: String,
+    #[pyo3(get)]
+    transition_count: usize,
+    #[pyo3(get)]
+    total_cost_usd: f64,
+    #[pyo3(get)]
+    created_at: String,
+    #[pyo3(get)]
+    last_model: Option<String>,
+    #[pyo3(get)]
+    last_modified: String,
+}
+
+/// Python wrapper for ConversationStore
+#[pyclass(name = "ConversationStore")]
+pub struct PyConversationStore {
+    inner: RustConversationStore,
+}
+
+#[pymethods]
+impl PyConversationStore {
+    #[new]
+    #[pyo3(signature = (dir=None))]
+    fn new(dir: Option<String>) -> PyResult<Self> {
+        let inner = RustConversationStore::new(dir.map(PathBuf::from))
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+        Ok(Self { inner })
+    }
+
+    fn create(&self, name: &str, workspace: &PyWorkspace) -> PyResult<PyConversation> {
+        let rust_workspace = Arc::new(
+            RustWorkspace::new(
+                workspace
+                    .inner
+                    .lock()
+                    .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("Lock error: {}", e)))?
+                    .path()
+                    .clone(),
+            )
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?,
+        );
+        let conversation = self
+            .inner
+            .create(name, rust_workspace)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+        Ok(PyConversation { inner: conversation })
+    }
+
+    fn list(&self) -> PyResult<Vec<PyConversationEntry>> {
+        let entries = self
+            .inner
+            .list()
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+        Ok(entries
+            .into_iter()
+            .map(|entry| PyConversationEntry {
+                name: entry.name,
+                id: entry.id.to_string(),
+                transition_count: entry.transition_count,
+                total_cost_usd: entry.total_cost_usd,
+                created_at: entry.created_at.to_rfc3339(),
+                last_model: entry.last_model,
+                last_modified: entry.last_modified.to_rfc3339(),
+            })
+            .collect())
+    }
+
+    /// Names only, for shell/REPL `.conversation` tab-completion.
+    fn names(&self) -> PyResult<Vec<String>> {
+        self.inner
+            .names()
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))
+    }
+
+    #[pyo3(signature = (name, workspace, record=true, strict=false))]
+    fn load(&self, name: &str, workspace: &PyWorkspace, record: bool, strict: bool) -> PyResult<PyConversation> {
+        let rust_workspace = Arc::new(
+            RustWorkspace::new(
+                workspace
+                    .inner
+                    .lock()
+                    .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("Lock error: {}", e)))?
+                    .path()
+                    .clone(),
+            )
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?,
+        );
+        let conversation = self
+            .inner
+            .load(name, rust_workspace, recording_policy(record, strict))
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+        Ok(PyConversation { inner: conversation })
+    }
+
+    /// Load the conversation stored under `name`, or start a brand-new one
+    /// against `workspace` if none exists yet.
+    #[pyo3(signature = (name, workspace, record=true, strict=false))]
+    fn open(&self, name: &str, workspace: &PyWorkspace, record: bool, strict: bool) -> PyResult<PyConversation> {
+        let rust_workspace = Arc::new(
+            RustWorkspace::new(
+                workspace
+                    .inner
+                    .lock()
+                    .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("Lock error: {}", e)))?
+                    .path()
+                    .clone(),
+            )
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?,
+        );
+        let conversation = self
+            .inner
+            .open(name, rust_workspace, recording_policy(record, strict))
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+        Ok(PyConversation { inner: conversation })
+    }
+
+    fn rename(&self, from: &str, to: &str) -> PyResult<()> {
+        self.inner
+            .rename(from, to)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))
+    }
+
+    fn delete(&self, name: &str) -> PyResult<()> {
+        self.inner
+            .delete(name)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))
     }
 }
 
@@ -347,9 +789,15 @@ impl PyEnvironmentSnapshot {
 pub fn register_execution_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<PyWorkspace>()?;
     m.add_class::<PyConversation>()?;
+    m.add_class::<PyConversationStore>()?;
+    m.add_class::<PyConversationEntry>()?;
     m.add_class::<PyTransition>()?;
+    m.add_class::<PyFileChange>()?;
     m.add_class::<PyClaudePrompt>()?;
     m.add_class::<PyClaudeExecution>()?;
     m.add_class::<PyEnvironmentSnapshot>()?;
+    m.add_class::<PyWatchHandle>()?;
+    m.add_class::<PyStreamEvent>()?;
+    m.add_class::<PyToolExecution>()?;
     Ok(())
 }
@@ -1,7 +1,7 @@
 use pyo3::prelude::*;
-use pyo3::types::{PyDict, PyList};
+use pyo3::types::{PyDateTime, PyDict, PyList};
 use serde_json::Value;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Datelike, TimeZone, Timelike};
 
 /// Convert a serde_json::Value to a Python object
 pub fn json_to_py(py: Python<'_>, value: &Value) -> PyResult<PyObject> {
@@ -37,9 +37,23 @@ pub fn json_to_py(py: Python<'_>, value: &Value) -> PyResult<PyObject> {
     }
 }
 
-/// Convert a chrono DateTime to a Python datetime object
-pub fn datetime_to_py(py: Python<'_>, dt: DateTime<Utc>) -> PyResult<PyObject> {
-    // PyO3 datetime support requires chrono-tz feature or manual conversion
-    // For now, return the ISO string representation
-    Ok(dt.to_rfc3339().to_object(py))
+/// Convert a chrono `DateTime<Tz>` to a genuine Python `datetime.datetime`
+/// (naive, built from `dt`'s own wall-clock fields in whatever timezone it
+/// carries), so callers can do arithmetic on it instead of re-parsing an ISO
+/// string. Callers that want local wall-clock output (e.g. `cost_series`
+/// with a `tz` argument) should convert via `dt.with_timezone(&tz)` before
+/// calling this, rather than pass a UTC instant straight through.
+pub fn datetime_to_py<Tz: TimeZone>(py: Python<'_>, dt: DateTime<Tz>) -> PyResult<PyObject> {
+    let py_dt = PyDateTime::new_bound(
+        py,
+        dt.year(),
+        dt.month() as u8,
+        dt.day() as u8,
+        dt.hour() as u8,
+        dt.minute() as u8,
+        dt.second() as u8,
+        dt.timestamp_subsec_micros(),
+        None,
+    )?;
+    Ok(py_dt.into())
 }
\ No newline at end of file
@@ -0,0 +1,85 @@
+use chrono::Utc;
+use claude_sdk::execution::{
+    CharsPerTokenEstimator, ClaudeExecution, ClaudePrompt, EnvironmentSnapshot, TokenEstimator,
+    Transition,
+};
+use claude_sdk::execution::compression;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use uuid::Uuid;
+
+fn fixture_transition(prompt_text: &str, response: &str) -> Transition {
+    let snapshot = EnvironmentSnapshot {
+        files: HashMap::new(),
+        session_file: PathBuf::from("none"),
+        session_id: None,
+        timestamp: Utc::now(),
+        session: None,
+    };
+
+    Transition {
+        id: Uuid::new_v4(),
+        before: snapshot.clone(),
+        prompt: ClaudePrompt {
+            text: prompt_text.to_string(),
+            continue_session: false,
+            resume_session_id: None,
+        },
+        execution: ClaudeExecution {
+            prompt: ClaudePrompt::default(),
+            response: response.to_string(),
+            session_id: "sess".to_string(),
+            cost: 0.0,
+            duration_ms: 0,
+            model: "test".to_string(),
+            timestamp: Utc::now(),
+        },
+        after: snapshot,
+        recorded_at: Utc::now(),
+        metadata: Default::default(),
+    }
+}
+
+#[test]
+fn test_chars_per_token_estimator() {
+    let estimator = CharsPerTokenEstimator::default();
+    assert_eq!(estimator.estimate("abcd"), 1);
+    assert_eq!(estimator.estimate("abcdefgh"), 2);
+    assert_eq!(estimator.estimate(""), 0);
+}
+
+#[test]
+fn test_compact_transitions_keeps_recent_and_folds_older() {
+    let estimator = CharsPerTokenEstimator::default();
+    // Responses long enough that folding them into one-line summaries
+    // actually saves tokens (the summary keeps only each prompt's first line).
+    let transitions: Vec<Transition> = (0..6)
+        .map(|i| fixture_transition(&format!("message {i}"), &format!("response {i} ").repeat(20)))
+        .collect();
+
+    let (compacted, report) = compression::compact_transitions(&transitions, 2, &estimator)
+        .expect("more than keep_recent transitions should compact");
+
+    // 1 synthetic summary transition + the 2 most recent kept verbatim.
+    assert_eq!(compacted.len(), 3);
+    assert_eq!(report.transitions_compacted, 4);
+    assert!(report.tokens_after < report.tokens_before);
+
+    // The kept transitions are untouched.
+    assert_eq!(compacted[1].prompt.text, "message 4");
+    assert_eq!(compacted[2].prompt.text, "message 5");
+
+    // The summary mentions every folded prompt.
+    assert!(compacted[0].execution.response.contains("message 0"));
+    assert!(compacted[0].execution.response.contains("message 3"));
+}
+
+#[test]
+fn test_compact_transitions_noop_under_keep_recent() {
+    let estimator = CharsPerTokenEstimator::default();
+    let transitions: Vec<Transition> = (0..2)
+        .map(|i| fixture_transition(&format!("message {i}"), &format!("response {i}")))
+        .collect();
+
+    assert!(compression::compact_transitions(&transitions, 4, &estimator).is_none());
+}
@@ -57,7 +57,7 @@ fn test_conversation_tools_used() {
         execution,
         after: snapshot,
         recorded_at: Utc::now(),
-        metadata: serde_json::Value::Null,
+        metadata: Default::default(),
     };
 
     // Build conversation JSON
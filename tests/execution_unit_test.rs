@@ -142,7 +142,7 @@ fn sample_transition() -> Transition {
         execution,
         after,
         recorded_at: now,
-        metadata: serde_json::Value::Null,
+        metadata: Default::default(),
     }
 }
 
@@ -1,5 +1,5 @@
 mod common;
-use claude_sdk::execution::{Conversation, Workspace};
+use claude_sdk::execution::{Conversation, RecordingPolicy, Workspace};
 use common::TestEnvironment;
 use std::sync::Arc;
 
@@ -11,13 +11,13 @@ fn test_recording_after_load() {
     let save_path = env.workspace.join("recording.json");
 
     {
-        let mut conv = Conversation::new_with_options(workspace.clone(), true).unwrap();
+        let mut conv = Conversation::new_with_options(workspace.clone(), RecordingPolicy::BestEffort).unwrap();
         conv.send("Create a file called foo.txt with 'hello'")
             .unwrap();
         conv.save(&save_path).unwrap();
     }
 
-    let mut conv = Conversation::load(&save_path, workspace.clone(), true).unwrap();
+    let mut conv = Conversation::load(&save_path, workspace.clone(), RecordingPolicy::BestEffort).unwrap();
     conv.send("Append ' world' to foo.txt").unwrap();
 
     let recorder = conv.recorder().expect("recorder missing");
@@ -53,7 +53,7 @@ fn test_transition_tool_extraction() {
         execution: exec,
         after: after_snap,
         recorded_at: Utc::now(),
-        metadata: serde_json::Value::Null,
+        metadata: Default::default(),
     };
 
     // Validate new messages
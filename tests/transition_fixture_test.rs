@@ -39,7 +39,7 @@ fn test_transition_followup_from_fixtures() {
         execution: exec,
         after: after_snap,
         recorded_at: Utc::now(),
-        metadata: serde_json::Value::Null,
+        metadata: Default::default(),
     };
 
     let new_msgs = transition.new_messages();
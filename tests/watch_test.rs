@@ -0,0 +1,54 @@
+use claude_sdk::execution::EnvironmentObserver;
+use std::fs;
+use std::sync::mpsc::RecvTimeoutError;
+use std::time::Duration;
+use tempfile::TempDir;
+
+#[test]
+fn test_watch_coalesces_burst_into_one_snapshot() {
+    let temp_dir = TempDir::new().unwrap();
+    let workspace = temp_dir.path().to_path_buf();
+    fs::write(workspace.join("a.txt"), "1").unwrap();
+
+    let observer = EnvironmentObserver::new(workspace.clone());
+    let (handle, rx) = observer.watch_channel().unwrap();
+
+    // A burst of rapid writes during the debounce window should collapse
+    // into a single snapshot.
+    for i in 0..5 {
+        fs::write(workspace.join("a.txt"), format!("{i}")).unwrap();
+        std::thread::sleep(Duration::from_millis(20));
+    }
+
+    let snapshot = rx
+        .recv_timeout(Duration::from_secs(2))
+        .expect("expected one coalesced snapshot");
+    assert!(snapshot.files.contains_key(&std::path::PathBuf::from("a.txt")));
+
+    // No second snapshot should follow immediately for the same burst.
+    match rx.recv_timeout(Duration::from_millis(300)) {
+        Err(RecvTimeoutError::Timeout) => {}
+        other => panic!("expected no further snapshot, got {other:?}"),
+    }
+
+    handle.stop();
+}
+
+#[test]
+fn test_watch_ignores_hidden_paths() {
+    let temp_dir = TempDir::new().unwrap();
+    let workspace = temp_dir.path().to_path_buf();
+
+    let observer = EnvironmentObserver::new(workspace.clone());
+    let (handle, rx) = observer.watch_channel().unwrap();
+
+    fs::create_dir(workspace.join(".git")).unwrap();
+    fs::write(workspace.join(".git").join("HEAD"), "ref: refs/heads/main").unwrap();
+
+    match rx.recv_timeout(Duration::from_millis(600)) {
+        Err(RecvTimeoutError::Timeout) => {}
+        other => panic!("expected writes under .git to be ignored, got {other:?}"),
+    }
+
+    handle.stop();
+}